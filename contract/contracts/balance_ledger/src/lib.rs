@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, Vec};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -35,6 +35,7 @@ enum DataKey {
     BackendSigner,
     Balance(Address),
     Metrics(Address),
+    Receivable(Address),
 }
 
 #[contract]
@@ -92,6 +93,75 @@ impl BalanceLedgerContract {
         Ok(updated)
     }
 
+    /// Apply balance changes for several users in one call. Entries are
+    /// `(user, withdrawable_delta, locked_delta)`; if any entry fails, none
+    /// of them is applied.
+    pub fn apply_deltas(
+        env: Env,
+        deltas: Vec<(Address, i128, i128)>,
+    ) -> Result<(), BalanceLedgerError> {
+        Self::require_backend_auth(&env)?;
+
+        for (user, withdrawable_delta, locked_delta) in deltas.iter() {
+            let previous = get_user_balance(&env, &user);
+            let updated = apply_balance_delta(&previous, withdrawable_delta, locked_delta)?;
+
+            store_user_balance(&env, &user, &updated);
+            publish_balance_updated_event(&env, &user, &previous, &updated);
+        }
+        Ok(())
+    }
+
+    /// Apply a correction to a user's withdrawable balance. A credit first
+    /// repays any receivable the user owes; a debit larger than the
+    /// withdrawable balance empties it and records the rest as receivable
+    /// instead of failing. Returns the user's receivable after the change.
+    pub fn apply_correction(
+        env: Env,
+        user: Address,
+        withdrawable_delta: i128,
+    ) -> Result<i128, BalanceLedgerError> {
+        Self::require_backend_auth(&env)?;
+
+        let previous = get_user_balance(&env, &user);
+        let owed = get_user_receivable(&env, &user);
+        let (withdrawable, receivable) = if withdrawable_delta >= 0 {
+            let repaid = withdrawable_delta.min(owed);
+            (
+                checked_add(previous.withdrawable, withdrawable_delta - repaid)?,
+                owed - repaid,
+            )
+        } else {
+            let clawback = withdrawable_delta
+                .checked_neg()
+                .ok_or(BalanceLedgerError::Overflow)?;
+            let covered = clawback.min(previous.withdrawable);
+            (
+                previous.withdrawable - covered,
+                checked_add(owed, clawback - covered)?,
+            )
+        };
+
+        let updated = UserBalance {
+            withdrawable,
+            locked: previous.locked,
+        };
+        store_user_balance(&env, &user, &updated);
+        publish_balance_updated_event(&env, &user, &previous, &updated);
+
+        if receivable != owed {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Receivable(user.clone()), &receivable);
+            #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+            env.events().publish(
+                (Symbol::new(&env, "receivable_updated"), user),
+                (owed, receivable),
+            );
+        }
+        Ok(receivable)
+    }
+
     pub fn lock_funds(
         env: Env,
         user: Address,
@@ -146,6 +216,12 @@ impl BalanceLedgerContract {
         get_user_balance(&env, &user).locked
     }
 
+    /// Amount a user owes after a correction clawed back more than their
+    /// withdrawable balance
+    pub fn get_receivable(env: Env, user: Address) -> i128 {
+        get_user_receivable(&env, &user)
+    }
+
     pub fn get_total(env: Env, user: Address) -> Result<i128, BalanceLedgerError> {
         let balance = get_user_balance(&env, &user);
         checked_add(balance.withdrawable, balance.locked)
@@ -246,6 +322,13 @@ fn store_user_balance(env: &Env, user: &Address, balance: &UserBalance) {
         .set(&DataKey::Balance(user.clone()), balance);
 }
 
+fn get_user_receivable(env: &Env, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Receivable(user.clone()))
+        .unwrap_or(0)
+}
+
 fn get_user_metrics(env: &Env, user: &Address) -> UserMetrics {
     env.storage()
         .persistent()
@@ -269,6 +352,7 @@ fn publish_balance_updated_event(
     previous: &UserBalance,
     updated: &UserBalance,
 ) {
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
     env.events().publish(
         (Symbol::new(env, "balance_updated"), user.clone()),
         (
@@ -288,6 +372,7 @@ fn publish_metrics_updated_event(
     lost_delta: i128,
     totals: &UserMetrics,
 ) {
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
     env.events().publish(
         (Symbol::new(env, "metrics_updated"), user.clone()),
         (
@@ -83,6 +83,67 @@ fn apply_delta_updates_both_buckets_atomically() {
     assert_eq!(updated.locked, 200);
 }
 
+#[test]
+fn apply_deltas_updates_all_users_or_none() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let backend = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contract_id = env.register(BalanceLedgerContract, ());
+    let client = BalanceLedgerContractClient::new(&env, &contract_id);
+
+    client.initialize(&backend);
+    client.set_balance(&alice, &0, &100);
+    client.set_balance(&bob, &0, &50);
+
+    client.apply_deltas(&soroban_sdk::vec![
+        &env,
+        (alice.clone(), 250, -100),
+        (bob.clone(), 0, -50),
+    ]);
+    assert_eq!(client.get_balance(&alice).withdrawable, 250);
+    assert_eq!(client.get_balance(&alice).locked, 0);
+    assert_eq!(client.get_balance(&bob).locked, 0);
+
+    assert_eq!(
+        client.try_apply_deltas(&soroban_sdk::vec![
+            &env,
+            (alice.clone(), -50, 0),
+            (bob.clone(), 0, -1),
+        ]),
+        Err(Ok(BalanceLedgerError::InsufficientLocked))
+    );
+    assert_eq!(client.get_balance(&alice).withdrawable, 250);
+}
+
+#[test]
+fn corrections_track_clawback_shortfall_as_receivable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let backend = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(BalanceLedgerContract, ());
+    let client = BalanceLedgerContractClient::new(&env, &contract_id);
+
+    client.initialize(&backend);
+    client.set_balance(&user, &60, &40);
+
+    assert_eq!(client.apply_correction(&user, &-100), 40);
+    assert_eq!(client.get_balance(&user).withdrawable, 0);
+    assert_eq!(client.get_balance(&user).locked, 40);
+    assert_eq!(client.get_receivable(&user), 40);
+
+    // Credits repay the receivable before reaching the balance
+    assert_eq!(client.apply_correction(&user, &30), 10);
+    assert_eq!(client.get_balance(&user).withdrawable, 0);
+    assert_eq!(client.apply_correction(&user, &25), 0);
+    assert_eq!(client.get_balance(&user).withdrawable, 15);
+    assert_eq!(client.get_receivable(&user), 0);
+}
+
 #[test]
 fn rejects_invalid_or_insufficient_updates() {
     let env = Env::default();
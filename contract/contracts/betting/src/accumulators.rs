@@ -0,0 +1,242 @@
+use common::{ContractError, ODDS_SCALE};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol, Vec, U256};
+
+use crate::bets::BetState;
+use crate::exposure;
+use crate::matches::{self, MatchStatus};
+use crate::odds;
+use crate::DataKey;
+
+pub const MIN_LEGS: u32 = 2;
+pub const MAX_LEGS: u32 = 12;
+
+/// Result of a single selection on a match, recorded by the admin.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SelectionResult {
+    Pending,
+    Won,
+    Lost,
+    Void,
+}
+
+/// A selection requested by the bettor when placing an accumulator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Selection {
+    pub match_id: BytesN<32>,
+    pub bet_type: Symbol,
+    pub odds: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccumulatorLeg {
+    pub match_id: BytesN<32>,
+    pub bet_type: Symbol,
+    pub odds: u32,
+    pub result: SelectionResult,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Accumulator {
+    pub acca_id: U256,
+    pub bettor: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub legs: Vec<AccumulatorLeg>,
+    pub combined_odds: i128,
+    pub timestamp: u64,
+    pub state: BetState,
+    pub payout: i128,
+}
+
+pub fn get_accumulator(env: &Env, acca_id: &U256) -> Result<Accumulator, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Accumulator(acca_id.clone()))
+        .ok_or(ContractError::BetNotFound)
+}
+
+pub fn store_accumulator(env: &Env, acca: &Accumulator) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Accumulator(acca.acca_id.clone()), acca);
+}
+
+pub fn get_user_accumulators(env: &Env, user: &Address) -> Vec<U256> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserAccumulators(user.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_user_accumulators(env: &Env, user: &Address, ids: &Vec<U256>) {
+    let key = DataKey::UserAccumulators(user.clone());
+    if ids.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, ids);
+    }
+}
+
+/// Validate the requested selections and turn them into pending legs.
+///
+/// Every leg must be on a different match, and every match must currently
+/// be accepting bets. Each leg is priced like a single bet, at the house's
+/// current odds for the selection.
+pub fn build_legs(
+    env: &Env,
+    selections: &Vec<Selection>,
+) -> Result<Vec<AccumulatorLeg>, ContractError> {
+    if selections.len() < MIN_LEGS || selections.len() > MAX_LEGS {
+        return Err(ContractError::InvalidBet);
+    }
+
+    let mut legs: Vec<AccumulatorLeg> = Vec::new(env);
+    for selection in selections.iter() {
+        if selection.odds == 0 {
+            return Err(ContractError::InvalidBet);
+        }
+        if legs.iter().any(|leg| leg.match_id == selection.match_id) {
+            return Err(ContractError::InvalidBet);
+        }
+        matches::ensure_betting_open(env, &selection.match_id)?;
+        let odds = odds::price_bet(
+            env,
+            &selection.match_id,
+            &selection.bet_type,
+            selection.odds,
+            0,
+        )?;
+
+        legs.push_back(AccumulatorLeg {
+            match_id: selection.match_id,
+            bet_type: selection.bet_type,
+            odds,
+            result: SelectionResult::Pending,
+        });
+    }
+    Ok(legs)
+}
+
+/// Product of the odds of every leg that is not void, scaled by `ODDS_SCALE`.
+pub fn combined_odds(legs: &Vec<AccumulatorLeg>) -> Result<i128, ContractError> {
+    let mut combined = ODDS_SCALE;
+    for leg in legs.iter() {
+        if leg.result == SelectionResult::Void {
+            continue;
+        }
+        combined = combined
+            .checked_mul(leg.odds as i128)
+            .ok_or(ContractError::InvalidAmount)?
+            / ODDS_SCALE;
+    }
+    Ok(combined)
+}
+
+/// Potential payout of an accumulator at its current combined odds.
+pub fn potential_payout(acca: &Accumulator) -> Result<i128, ContractError> {
+    acca.amount
+        .checked_mul(acca.combined_odds)
+        .map(|payout| payout / ODDS_SCALE)
+        .ok_or(ContractError::InvalidAmount)
+}
+
+/// Count an accumulator's potential payout against the exposure of every
+/// leg's selection, since any leg could complete a winning accumulator.
+pub fn reserve(env: &Env, acca: &Accumulator) -> Result<(), ContractError> {
+    let liability = potential_payout(acca)?;
+    for leg in acca.legs.iter() {
+        exposure::reserve_liability(env, &leg.match_id, &leg.bet_type, liability)?;
+    }
+    Ok(())
+}
+
+/// Remove the exposure reserved for an accumulator at placement.
+pub fn release(env: &Env, acca: &Accumulator, liability: i128) {
+    for leg in acca.legs.iter() {
+        exposure::release_liability(env, &leg.match_id, &leg.bet_type, liability);
+    }
+}
+
+pub fn get_selection_result(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+) -> SelectionResult {
+    if let Ok(record) = matches::get_match(env, match_id) {
+        if record.status == MatchStatus::Void {
+            return SelectionResult::Void;
+        }
+    }
+    env.storage()
+        .persistent()
+        .get(&DataKey::SelectionResult(
+            match_id.clone(),
+            bet_type.clone(),
+        ))
+        .unwrap_or(SelectionResult::Pending)
+}
+
+/// Result a selection settles at, once its match is final. A selection the
+/// operator never recorded on a settled match counts as lost, so it cannot
+/// hold up claims or sweeps. Returns `None` while the match is not final.
+pub fn settled_result(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+) -> Option<SelectionResult> {
+    let status = matches::get_match(env, match_id).ok()?.status;
+    match (status, get_selection_result(env, match_id, bet_type)) {
+        (MatchStatus::Void, _) => Some(SelectionResult::Void),
+        (MatchStatus::Settled, SelectionResult::Pending) => Some(SelectionResult::Lost),
+        (MatchStatus::Settled, result) => Some(result),
+        _ => None,
+    }
+}
+
+/// Pull in the results of legs whose match is final and decide the
+/// accumulator.
+///
+/// The accumulator loses as soon as one leg loses. It is paid only once
+/// every leg is decided, with void legs dropped from the combined odds; if
+/// every leg is void the stake is refunded.
+pub fn resolve(env: &Env, acca: &mut Accumulator) -> Result<(), ContractError> {
+    let mut legs: Vec<AccumulatorLeg> = Vec::new(env);
+    let mut any_lost = false;
+    let mut all_decided = true;
+    let mut all_void = true;
+
+    for mut leg in acca.legs.iter() {
+        if leg.result == SelectionResult::Pending {
+            leg.result = settled_result(env, &leg.match_id, &leg.bet_type)
+                .unwrap_or(SelectionResult::Pending);
+        }
+        match leg.result {
+            SelectionResult::Lost => {
+                any_lost = true;
+                all_void = false;
+            }
+            SelectionResult::Won => all_void = false,
+            SelectionResult::Void => {}
+            SelectionResult::Pending => all_decided = false,
+        }
+        legs.push_back(leg);
+    }
+    acca.legs = legs;
+
+    if any_lost {
+        acca.state = BetState::Lost;
+        acca.payout = 0;
+    } else if all_decided && all_void {
+        acca.state = BetState::Void;
+        acca.payout = acca.amount;
+    } else if all_decided {
+        acca.combined_odds = combined_odds(&acca.legs)?;
+        acca.state = BetState::Won;
+        acca.payout = potential_payout(acca)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use common::ContractError;
+use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol, Vec, U256};
+
+use crate::exposure;
+use crate::DataKey;
+
+pub use common::{Bet, BetState};
+
+/// One single bet of a bet slip submitted in a batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetRequest {
+    pub amount: i128,
+    pub match_id: BytesN<32>,
+    pub bet_type: Symbol,
+    pub odds: u32,
+}
+
+pub fn next_bet_id(env: &Env) -> U256 {
+    let storage = env.storage().persistent();
+    let current: U256 = storage
+        .get(&DataKey::BetCounter)
+        .unwrap_or_else(|| U256::from_u32(env, 0));
+    let next = current.add(&U256::from_u32(env, 1));
+    storage.set(&DataKey::BetCounter, &next);
+    next
+}
+
+pub fn get_bet(env: &Env, bet_id: &U256) -> Result<Bet, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Bet(bet_id.clone()))
+        .ok_or(ContractError::BetNotFound)
+}
+
+pub fn store_bet(env: &Env, bet: &Bet) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Bet(bet.bet_id.clone()), bet);
+}
+
+pub fn get_user_active_bets(env: &Env, user: &Address) -> Vec<U256> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserBets(user.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn match_bet_count(env: &Env, match_id: &BytesN<32>) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MatchBetCount(match_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn bettor_match_bet_count(env: &Env, match_id: &BytesN<32>, bettor: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BettorMatchBets(match_id.clone(), bettor.clone()))
+        .unwrap_or(0)
+}
+
+/// Add a newly placed bet to the user, match and bettor-per-match indexes.
+pub fn index_bet(env: &Env, bet: &Bet) {
+    let storage = env.storage().persistent();
+
+    let mut user_bets = get_user_active_bets(env, &bet.bettor);
+    user_bets.push_back(bet.bet_id.clone());
+    storage.set(&DataKey::UserBets(bet.bettor.clone()), &user_bets);
+
+    let count = match_bet_count(env, &bet.match_id);
+    storage.set(&DataKey::MatchBet(bet.match_id.clone(), count), &bet.bet_id);
+    storage.set(&DataKey::MatchBetCount(bet.match_id.clone()), &(count + 1));
+
+    let bettor_count = bettor_match_bet_count(env, &bet.match_id, &bet.bettor);
+    storage.set(
+        &DataKey::BettorMatchBets(bet.match_id.clone(), bet.bettor.clone()),
+        &(bettor_count + 1),
+    );
+}
+
+/// Drop a bet that is no longer active from the user's active bets.
+///
+/// The per-match index is append-only so paging stays stable; consumers
+/// skip entries whose bet is no longer active.
+pub fn deactivate_bet(env: &Env, bet: &Bet) {
+    let storage = env.storage().persistent();
+
+    let mut user_bets = get_user_active_bets(env, &bet.bettor);
+    if let Some(index) = user_bets.first_index_of(&bet.bet_id) {
+        user_bets.remove(index);
+    }
+    if user_bets.is_empty() {
+        storage.remove(&DataKey::UserBets(bet.bettor.clone()));
+    } else {
+        storage.set(&DataKey::UserBets(bet.bettor.clone()), &user_bets);
+    }
+
+    let bettor_key = DataKey::BettorMatchBets(bet.match_id.clone(), bet.bettor.clone());
+    let bettor_count = bettor_match_bet_count(env, &bet.match_id, &bet.bettor);
+    if bettor_count > 1 {
+        storage.set(&bettor_key, &(bettor_count - 1));
+    } else {
+        storage.remove(&bettor_key);
+    }
+
+    exposure::release(env, bet);
+}
@@ -0,0 +1,25 @@
+use soroban_sdk::{contracttype, xdr::ToXdr, Bytes, BytesN, Env, U256};
+
+/// Domain tag prepended to every backend-signed cash-out quote
+const CASH_OUT_SIGNATURE_DOMAIN: &[u8] = b"renaissance:cashout:v1";
+
+/// Backend price for closing an open bet early. The nonce makes every quote
+/// single-use, even when the same amount is quoted twice for one bet.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CashOutQuote {
+    pub bet_id: U256,
+    pub amount: i128,
+    pub expires_at: u64,
+    pub nonce: BytesN<32>,
+}
+
+pub fn quote_payload(env: &Env, quote: &CashOutQuote) -> Bytes {
+    let mut payload = Bytes::from_slice(env, CASH_OUT_SIGNATURE_DOMAIN);
+    payload.append(&env.current_contract_address().to_xdr(env));
+    payload.append(&quote.bet_id.to_be_bytes());
+    payload.extend_from_array(&quote.amount.to_be_bytes());
+    payload.extend_from_array(&quote.expires_at.to_be_bytes());
+    payload.append(&Bytes::from_array(env, &quote.nonce.to_array()));
+    payload
+}
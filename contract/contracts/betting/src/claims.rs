@@ -0,0 +1,166 @@
+use common::{compute_payout, ContractError};
+use soroban_sdk::{token, vec, Address, BytesN, Env, IntoVal, Symbol, U256};
+
+use crate::accumulators::{self, SelectionResult};
+use crate::bets::{self, Bet, BetState};
+use crate::matches::{self, MatchStatus};
+use crate::refunds::MAX_REFUND_BATCH;
+use crate::responsible;
+use crate::DataKey;
+
+pub fn get_treasury(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Treasury)
+}
+
+pub fn get_settlement_contract(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::SettlementContract)
+}
+
+/// Whether the settlement contract has proposed, recorded or is settling
+/// the bet with its match
+pub fn in_settlement(env: &Env, bet: &Bet) -> bool {
+    let Some(settlement) = get_settlement_contract(env) else {
+        return false;
+    };
+    env.invoke_contract(
+        &settlement,
+        &Symbol::new(env, "has_settlement"),
+        vec![env, bet.bet_id.into_val(env), bet.match_id.into_val(env)],
+    )
+}
+
+/// Reject paying out a bet the settlement contract has proposed, recorded
+/// or is settling with its match, so it cannot be paid on both sides.
+pub fn ensure_not_in_settlement(env: &Env, bet: &Bet) -> Result<(), ContractError> {
+    if in_settlement(env, bet) {
+        return Err(ContractError::ProposalPending);
+    }
+    Ok(())
+}
+
+/// Move the escrowed stake of a bet settled through the balance ledger to
+/// the treasury, which backs ledger payouts. Winners are paid by the ledger,
+/// so their stake leaves escrow the same way as a loser's.
+pub fn release_to_treasury(env: &Env, bet: &Bet) -> Result<i128, ContractError> {
+    let stake = bet.funded_stake();
+    if stake > 0 {
+        let treasury = get_treasury(env).ok_or(ContractError::NotInitialized)?;
+        let token_client = token::Client::new(env, &bet.token);
+        token_client.transfer(&env.current_contract_address(), &treasury, &stake);
+    }
+    Ok(stake)
+}
+
+/// Final result of a single bet. Results only count once the match is
+/// settled, so a selection result recorded early cannot be claimed against.
+pub fn final_result(env: &Env, bet: &Bet) -> Result<SelectionResult, ContractError> {
+    let record = matches::get_match(env, &bet.match_id)?;
+    if record.status != MatchStatus::Settled {
+        return Err(ContractError::ResultNotFinal);
+    }
+    accumulators::settled_result(env, &bet.match_id, &bet.bet_type)
+        .ok_or(ContractError::ResultNotFinal)
+}
+
+/// Pay out a won bet, or refund a bet whose selection was voided, in the
+/// bet's token from escrow and the house bankroll. Free bets only pay their
+/// winnings, since the stake was never escrowed.
+pub fn claim(env: &Env, bet: &mut Bet) -> Result<i128, ContractError> {
+    if bet.state != BetState::Active {
+        return Err(ContractError::BetAlreadySettled);
+    }
+    ensure_not_in_settlement(env, bet)?;
+
+    let stake = bet.funded_stake();
+    let (state, amount) = match final_result(env, bet)? {
+        SelectionResult::Won => {
+            let payout =
+                compute_payout(bet.amount, bet.odds).ok_or(ContractError::InvalidAmount)?;
+            // Winnings, plus the stake back when the bettor paid for it
+            (BetState::Won, payout - bet.amount + stake)
+        }
+        SelectionResult::Void => (BetState::Void, stake),
+        _ => return Err(ContractError::NotClaimable),
+    };
+
+    let token_client = token::Client::new(env, &bet.token);
+    if token_client.balance(&env.current_contract_address()) < amount {
+        return Err(ContractError::InsufficientBalance);
+    }
+
+    bet.state = state;
+    bets::store_bet(env, bet);
+    bets::deactivate_bet(env, bet);
+    responsible::record_result(env, &bet.bettor, stake, amount);
+
+    if amount > 0 {
+        token_client.transfer(&env.current_contract_address(), &bet.bettor, &amount);
+    }
+
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events().publish(
+        (Symbol::new(env, "bet_claimed"), bet.bet_id.clone()),
+        (bet.bettor.clone(), bet.token.clone(), amount),
+    );
+    Ok(amount)
+}
+
+/// Move the stakes of lost bets on a settled match to the treasury, up to
+/// `batch_size` bets per call, resuming from the stored cursor. Bets the
+/// settlement contract is handling are skipped. Returns the number of bets
+/// on the match left to scan.
+pub fn sweep_batch(
+    env: &Env,
+    match_id: &BytesN<32>,
+    batch_size: u32,
+) -> Result<u32, ContractError> {
+    let record = matches::get_match(env, match_id)?;
+    if record.status != MatchStatus::Settled {
+        return Err(ContractError::ResultNotFinal);
+    }
+    let treasury = get_treasury(env).ok_or(ContractError::NotInitialized)?;
+
+    let storage = env.storage().persistent();
+    let cursor_key = DataKey::SweepCursor(match_id.clone());
+    let start: u32 = storage.get(&cursor_key).unwrap_or(0);
+    let total = bets::match_bet_count(env, match_id);
+    let end = total.min(start.saturating_add(batch_size.clamp(1, MAX_REFUND_BATCH)));
+
+    let mut next = start;
+    while next < end {
+        let key = DataKey::MatchBet(match_id.clone(), next);
+        if let Some(bet_id) = storage.get::<_, U256>(&key) {
+            let bet = bets::get_bet(env, &bet_id)?;
+            if bet.state == BetState::Active
+                && final_result(env, &bet)? == SelectionResult::Lost
+                && !in_settlement(env, &bet)
+            {
+                sweep_stake(env, &treasury, bet);
+            }
+        }
+        next += 1;
+    }
+
+    storage.set(&cursor_key, &next);
+    Ok(total - next)
+}
+
+fn sweep_stake(env: &Env, treasury: &Address, mut bet: Bet) {
+    bet.state = BetState::Lost;
+    bets::store_bet(env, &bet);
+    bets::deactivate_bet(env, &bet);
+
+    // A lost free bet leaves nothing in escrow to sweep
+    let stake = bet.funded_stake();
+    responsible::record_result(env, &bet.bettor, stake, 0);
+    if stake > 0 {
+        let token_client = token::Client::new(env, &bet.token);
+        token_client.transfer(&env.current_contract_address(), treasury, &stake);
+    }
+
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events().publish(
+        (Symbol::new(env, "stake_swept"), bet.bet_id.clone()),
+        (treasury.clone(), bet.token, stake),
+    );
+}
@@ -0,0 +1,114 @@
+use common::{settlement_payout, BetOutcome, ContractError};
+use soroban_sdk::{contracttype, BytesN, Env, Symbol};
+
+use crate::bets::Bet;
+use crate::DataKey;
+
+/// Maximum potential payout the house accepts on a match. A limit of zero
+/// means the liability is not capped.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiabilityLimits {
+    pub max_match_liability: i128,
+    pub max_selection_liability: i128,
+}
+
+pub fn get_limits(env: &Env, match_id: &BytesN<32>) -> LiabilityLimits {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LiabilityLimits(match_id.clone()))
+        .unwrap_or(LiabilityLimits {
+            max_match_liability: 0,
+            max_selection_liability: 0,
+        })
+}
+
+pub fn set_limits(env: &Env, match_id: &BytesN<32>, limits: &LiabilityLimits) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::LiabilityLimits(match_id.clone()), limits);
+}
+
+pub fn match_exposure(env: &Env, match_id: &BytesN<32>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MatchExposure(match_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn selection_exposure(env: &Env, match_id: &BytesN<32>, bet_type: &Symbol) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SelectionExposure(
+            match_id.clone(),
+            bet_type.clone(),
+        ))
+        .unwrap_or(0)
+}
+
+/// Add the potential payout of a new bet to the match and selection
+/// exposure, rejecting it if either configured limit would be exceeded. A
+/// free bet only pays its winnings, so its stake is not part of the payout.
+pub fn reserve(env: &Env, bet: &Bet) -> Result<(), ContractError> {
+    let liability = settlement_payout(bet, BetOutcome::Win).ok_or(ContractError::InvalidAmount)?;
+    reserve_liability(env, &bet.match_id, &bet.bet_type, liability)
+}
+
+/// Remove the potential payout of a bet that is no longer active.
+pub fn release(env: &Env, bet: &Bet) {
+    let liability = settlement_payout(bet, BetOutcome::Win).unwrap_or(0);
+    release_liability(env, &bet.match_id, &bet.bet_type, liability);
+}
+
+/// Add `liability` to the exposure of a selection and its match, rejecting
+/// it if either configured limit would be exceeded.
+pub fn reserve_liability(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+    liability: i128,
+) -> Result<(), ContractError> {
+    let limits = get_limits(env, match_id);
+
+    let match_total = match_exposure(env, match_id)
+        .checked_add(liability)
+        .ok_or(ContractError::InvalidAmount)?;
+    let selection_total = selection_exposure(env, match_id, bet_type)
+        .checked_add(liability)
+        .ok_or(ContractError::InvalidAmount)?;
+
+    if exceeds(match_total, limits.max_match_liability)
+        || exceeds(selection_total, limits.max_selection_liability)
+    {
+        return Err(ContractError::LiabilityExceeded);
+    }
+
+    store(env, match_id, bet_type, match_total, selection_total);
+    Ok(())
+}
+
+/// Remove `liability` from the exposure of a selection and its match.
+pub fn release_liability(env: &Env, match_id: &BytesN<32>, bet_type: &Symbol, liability: i128) {
+    let match_total = (match_exposure(env, match_id) - liability).max(0);
+    let selection_total = (selection_exposure(env, match_id, bet_type) - liability).max(0);
+    store(env, match_id, bet_type, match_total, selection_total);
+}
+
+fn exceeds(total: i128, limit: i128) -> bool {
+    limit > 0 && total > limit
+}
+
+fn store(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+    match_total: i128,
+    selection_total: i128,
+) {
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::MatchExposure(match_id.clone()), &match_total);
+    storage.set(
+        &DataKey::SelectionExposure(match_id.clone(), bet_type.clone()),
+        &selection_total,
+    );
+}
@@ -0,0 +1,63 @@
+use common::ContractError;
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec, U256};
+
+use crate::DataKey;
+
+/// Domain tag prepended to every backend-signed free-bet voucher
+const FREE_BET_SIGNATURE_DOMAIN: &[u8] = b"renaissance:freebet:v1";
+
+/// Backend-issued stake for a single bet. The stake is not escrowed: a
+/// winning free bet pays its winnings without the stake, and a losing or
+/// voided one pays nothing. An empty `markets` list makes the voucher valid
+/// on any match.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreeBetVoucher {
+    pub voucher_id: BytesN<32>,
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub expires_at: u64,
+    pub markets: Vec<BytesN<32>>,
+}
+
+pub fn voucher_payload(env: &Env, voucher: &FreeBetVoucher) -> Bytes {
+    let mut payload = Bytes::from_slice(env, FREE_BET_SIGNATURE_DOMAIN);
+    payload.append(&env.current_contract_address().to_xdr(env));
+    payload.append(&Bytes::from_array(env, &voucher.voucher_id.to_array()));
+    payload.append(&voucher.user.clone().to_xdr(env));
+    payload.append(&voucher.token.clone().to_xdr(env));
+    payload.extend_from_array(&voucher.amount.to_be_bytes());
+    payload.extend_from_array(&voucher.expires_at.to_be_bytes());
+    for match_id in voucher.markets.iter() {
+        payload.append(&Bytes::from_array(env, &match_id.to_array()));
+    }
+    payload
+}
+
+pub fn ensure_eligible(
+    voucher: &FreeBetVoucher,
+    match_id: &BytesN<32>,
+) -> Result<(), ContractError> {
+    if voucher.amount <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    if !voucher.markets.is_empty() && !voucher.markets.contains(match_id) {
+        return Err(ContractError::InvalidBet);
+    }
+    Ok(())
+}
+
+/// Voucher a bet was placed with, if it is a free bet
+pub fn voucher_of(env: &Env, bet_id: &U256) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FreeBet(bet_id.clone()))
+}
+
+/// Record the voucher a free bet was placed with
+pub fn record(env: &Env, bet_id: &U256, voucher_id: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FreeBet(bet_id.clone()), voucher_id);
+}
@@ -1,105 +1,1224 @@
 #![no_std]
 use common::{
-    cleanup_operation, ensure_not_replayed, is_operation_executed, BetPlacedEvent, ContractError,
-    SpinExecutedEvent,
+    cleanup_operation, create_bet_event, ensure_not_paused, ensure_not_replayed,
+    is_operation_executed, BetPlacedEvent, ContractError, PauseScope, SpinExecutedEvent, BET_EVENT,
 };
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, BytesN, Env, Map, Symbol};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol,
+    Vec, U256,
+};
+
+mod accumulators;
+mod bets;
+mod cashout;
+mod claims;
+mod exposure;
+mod freebets;
+mod matches;
+mod odds;
+mod pools;
+mod refunds;
+mod responsible;
+mod spins;
+mod tokens;
+
+pub use accumulators::{Accumulator, AccumulatorLeg, Selection, SelectionResult};
+pub use bets::{Bet, BetRequest, BetState};
+pub use cashout::CashOutQuote;
+pub use exposure::LiabilityLimits;
+pub use freebets::FreeBetVoucher;
+pub use matches::{Match, MatchStatus};
+pub use odds::OddsPublication;
+pub use pools::{Pool, PoolStatus};
+pub use refunds::VoidProgress;
+pub use responsible::{GamingLimits, PendingLimits, WagerTotals};
+pub use spins::SpinExecution;
+pub use tokens::TokenConfig;
+
+/// Upper bound on bets placed in one `place_bets` call
+const MAX_BATCH_BETS: u32 = 20;
+
+/// Cancellation fees are expressed in basis points of the stake
+const MAX_FEE_BPS: u32 = 10_000;
+
+/// Domain tag prepended to every backend-signed spin payload
+const SPIN_SIGNATURE_DOMAIN: &[u8] = b"renaissance:spin:v1";
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Operator,
+    BackendPublicKey,
+    UsedSpinHashes,
+    /// Legacy map of every spin execution, drained by `migrate_spin_executions`
+    SpinExecutions,
+    SpinExecution(BytesN<32>),
+    Bet(U256),
+    BetCounter,
+    UserBets(Address),
+    MatchBetCount(BytesN<32>),
+    MatchBet(BytesN<32>, u32),
+    BettorMatchBets(BytesN<32>, Address),
+    PreventDoubleBetting,
+    Match(BytesN<32>),
+    CancellationFeeBps,
+    CollectedFees(Address),
+    VoidProgress(BytesN<32>),
+    Accumulator(U256),
+    UserAccumulators(Address),
+    SelectionResult(BytesN<32>, Symbol),
+    LiabilityLimits(BytesN<32>),
+    MatchExposure(BytesN<32>),
+    SelectionExposure(BytesN<32>, Symbol),
+    AllowedTokens,
+    TokenConfig(Address),
+    Treasury,
+    SettlementContract,
+    SweepCursor(BytesN<32>),
+    Pool(BytesN<32>),
+    PoolOutcomeTotal(BytesN<32>, Symbol),
+    PoolStake(BytesN<32>, Symbol, Address),
+    GamingLimits(Address),
+    PendingGamingLimits(Address),
+    WagerTotals(Address),
+    SelfExclusion(Address),
+    LiveOdds(BytesN<32>, Symbol),
+    OddsStaleness,
+    LegacyBetEvents,
+    FreeBet(U256),
+}
+
+#[contract]
+pub struct BettingContract;
+
+#[contractimpl]
+impl BettingContract {
+    /// Set the contract's admin and operator at deployment. The admin
+    /// manages configuration and the backend signer; the operator runs
+    /// matches and markets.
+    pub fn __constructor(env: Env, admin: Address, operator: Address) {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::Operator, &operator);
+    }
+
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Admin)
+    }
+
+    pub fn get_operator(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Operator)
+    }
+
+    /// Replace the operator. Only the admin can call this.
+    pub fn set_operator(env: Env, admin: Address, operator: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Operator, &operator);
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "operator_set"),), operator);
+        Ok(())
+    }
+
+    /// Place a bet and escrow funds. Returns the newly allocated bet ID.
+    ///
+    /// The bet is taken at the selection's current published price, and
+    /// rejected if the selection has no price or it is below `odds`.
+    pub fn place_bet(
+        env: Env,
+        bettor: Address,
+        token_address: Address,
+        amount: i128,
+        match_id: BytesN<32>,
+        bet_type: Symbol,
+        odds: u32,
+    ) -> Result<U256, ContractError> {
+        ensure_not_paused(&env, PauseScope::Bets)?;
+        bettor.require_auth();
+
+        let odds = odds::price_bet(&env, &match_id, &bet_type, odds, 0)?;
+        let bet_id = record_bet(
+            &env,
+            &bettor,
+            &token_address,
+            amount,
+            &match_id,
+            &bet_type,
+            odds,
+            false,
+        )?;
+
+        // Lock funds (transfer from bettor to contract)
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&bettor, env.current_contract_address(), &amount);
+
+        Ok(bet_id)
+    }
+
+    /// Place several single bets in one transaction, escrowing their total
+    /// stake with a single transfer. Every bet is validated as in
+    /// `place_bet`; if any is rejected, none is placed. Returns the new bet
+    /// IDs in request order.
+    pub fn place_bets(
+        env: Env,
+        bettor: Address,
+        token_address: Address,
+        requests: Vec<BetRequest>,
+    ) -> Result<Vec<U256>, ContractError> {
+        ensure_not_paused(&env, PauseScope::Bets)?;
+        bettor.require_auth();
+
+        if requests.is_empty() || requests.len() > MAX_BATCH_BETS {
+            return Err(ContractError::InvalidBet);
+        }
+
+        let mut bet_ids = Vec::new(&env);
+        let mut total = 0i128;
+        for request in requests.iter() {
+            let odds =
+                odds::price_bet(&env, &request.match_id, &request.bet_type, request.odds, 0)?;
+            let bet_id = record_bet(
+                &env,
+                &bettor,
+                &token_address,
+                request.amount,
+                &request.match_id,
+                &request.bet_type,
+                odds,
+                false,
+            )?;
+            total = total
+                .checked_add(request.amount)
+                .ok_or(ContractError::InvalidAmount)?;
+            bet_ids.push_back(bet_id);
+        }
+
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&bettor, env.current_contract_address(), &total);
+
+        Ok(bet_ids)
+    }
+
+    /// Place an in-play bet at the current published odds of a selection,
+    /// which must be within the staleness window. The bet is re-priced to
+    /// the current odds as long as they have not dropped more than
+    /// `max_slippage_bps` below `accepted_odds`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_live_bet(
+        env: Env,
+        bettor: Address,
+        token_address: Address,
+        amount: i128,
+        match_id: BytesN<32>,
+        bet_type: Symbol,
+        accepted_odds: u32,
+        max_slippage_bps: u32,
+    ) -> Result<U256, ContractError> {
+        ensure_not_paused(&env, PauseScope::Bets)?;
+        bettor.require_auth();
+
+        if max_slippage_bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidAmount);
+        }
+        let odds =
+            odds::price_live_bet(&env, &match_id, &bet_type, accepted_odds, max_slippage_bps)?;
+        let bet_id = record_bet(
+            &env,
+            &bettor,
+            &token_address,
+            amount,
+            &match_id,
+            &bet_type,
+            odds,
+            false,
+        )?;
+
+        // Lock funds (transfer from bettor to contract)
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&bettor, env.current_contract_address(), &amount);
+
+        Ok(bet_id)
+    }
+
+    /// Build the domain-separated payload the backend signs for a free-bet
+    /// voucher
+    pub fn free_bet_signing_payload(env: Env, voucher: FreeBetVoucher) -> Bytes {
+        freebets::voucher_payload(&env, &voucher)
+    }
+
+    /// Place a bet funded by a backend-signed free-bet voucher instead of a
+    /// token transfer. Each voucher can be redeemed once. Returns the newly
+    /// allocated bet ID.
+    pub fn place_free_bet(
+        env: Env,
+        bettor: Address,
+        voucher: FreeBetVoucher,
+        signature: BytesN<64>,
+        match_id: BytesN<32>,
+        bet_type: Symbol,
+        odds: u32,
+    ) -> Result<U256, ContractError> {
+        ensure_not_paused(&env, PauseScope::Bets)?;
+        bettor.require_auth();
+
+        if voucher.user != bettor {
+            return Err(ContractError::Unauthorized);
+        }
+        freebets::ensure_eligible(&voucher, &match_id)?;
+
+        let public_key: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BackendPublicKey)
+            .ok_or(ContractError::InvalidSignature)?;
+        if env.ledger().timestamp() > voucher.expires_at {
+            return Err(ContractError::SignatureExpired);
+        }
+        let payload = freebets::voucher_payload(&env, &voucher);
+        env.crypto()
+            .ed25519_verify(&public_key, &payload, &signature);
+
+        ensure_not_replayed(
+            &env,
+            Symbol::new(&env, "free_bet"),
+            voucher.voucher_id.clone(),
+            None,
+        )?;
+
+        let odds = odds::price_bet(&env, &match_id, &bet_type, odds, 0)?;
+        let bet_id = record_bet(
+            &env,
+            &bettor,
+            &voucher.token,
+            voucher.amount,
+            &match_id,
+            &bet_type,
+            odds,
+            true,
+        )?;
+        freebets::record(&env, &bet_id, &voucher.voucher_id);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "free_bet_redeemed"), voucher.voucher_id),
+            (bettor, bet_id.clone()),
+        );
+        Ok(bet_id)
+    }
+
+    /// Check whether a free-bet voucher has been redeemed
+    pub fn is_voucher_redeemed(env: Env, voucher_id: BytesN<32>) -> bool {
+        is_operation_executed(&env, Symbol::new(&env, "free_bet"), voucher_id)
+    }
+
+    /// Get the voucher a free bet was placed with, if any
+    pub fn get_free_bet_voucher(env: Env, bet_id: U256) -> Option<BytesN<32>> {
+        freebets::voucher_of(&env, &bet_id)
+    }
+
+    /// Build the domain-separated payload the backend signs for an odds
+    /// publication
+    pub fn odds_signing_payload(env: Env, publication: OddsPublication) -> Bytes {
+        odds::publication_payload(&env, &publication)
+    }
+
+    /// Record a backend-signed odds publication as the current price of its
+    /// selection. Anyone may relay a publication; it must be newer than the
+    /// current version and within the staleness window.
+    pub fn publish_odds(
+        env: Env,
+        publication: OddsPublication,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        let public_key: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BackendPublicKey)
+            .ok_or(ContractError::InvalidSignature)?;
+        let payload = odds::publication_payload(&env, &publication);
+        env.crypto()
+            .ed25519_verify(&public_key, &payload, &signature);
+
+        odds::publish(&env, &publication)?;
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "odds_published"), publication.match_id),
+            (publication.bet_type, publication.odds, publication.version),
+        );
+        Ok(())
+    }
+
+    /// Get the current published odds of a selection
+    pub fn get_live_odds(
+        env: Env,
+        match_id: BytesN<32>,
+        bet_type: Symbol,
+    ) -> Option<OddsPublication> {
+        odds::get_current(&env, &match_id, &bet_type)
+    }
+
+    /// Set how old, in seconds, an odds publication may be when it is
+    /// relayed or an in-play bet is placed at it
+    pub fn set_odds_staleness(env: Env, admin: Address, seconds: u64) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if seconds == 0 {
+            return Err(ContractError::InvalidTimeWindow);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::OddsStaleness, &seconds);
+        Ok(())
+    }
+
+    /// Get the maximum age, in seconds, of an accepted odds publication
+    pub fn get_odds_staleness(env: Env) -> u64 {
+        odds::staleness_window(&env)
+    }
+
+    /// Place an accumulator: one stake over 2-12 selections on different
+    /// matches, paid at the product of the selections' odds. Each leg is
+    /// taken at the house's current price and counts against the liability
+    /// limits of its selection. Returns the bet ID, allocated from the same
+    /// sequence as single bets.
+    pub fn place_accumulator(
+        env: Env,
+        bettor: Address,
+        token_address: Address,
+        amount: i128,
+        selections: Vec<Selection>,
+    ) -> Result<U256, ContractError> {
+        ensure_not_paused(&env, PauseScope::Bets)?;
+        bettor.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        tokens::ensure_stake_allowed(&env, &token_address, amount)?;
+        let legs = accumulators::build_legs(&env, &selections)?;
+        responsible::record_wager(&env, &bettor, amount, amount)?;
+        let combined_odds = accumulators::combined_odds(&legs)?;
+
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&bettor, env.current_contract_address(), &amount);
+
+        let acca_id = bets::next_bet_id(&env);
+        let acca = Accumulator {
+            acca_id: acca_id.clone(),
+            bettor: bettor.clone(),
+            token: token_address,
+            amount,
+            legs,
+            combined_odds,
+            timestamp: env.ledger().timestamp(),
+            state: BetState::Active,
+            payout: 0,
+        };
+        // Reject the stake if its potential payout breaches a liability limit
+        accumulators::reserve(&env, &acca)?;
+        accumulators::store_accumulator(&env, &acca);
+
+        let mut user_accas = accumulators::get_user_accumulators(&env, &bettor);
+        user_accas.push_back(acca_id.clone());
+        accumulators::set_user_accumulators(&env, &bettor, &user_accas);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "acca_placed"), acca_id.clone()),
+            (bettor, amount, combined_odds),
+        );
+
+        Ok(acca_id)
+    }
+
+    /// Record the result of a selection on a match, used to decide
+    /// accumulator legs. Results can only be recorded while the match is
+    /// locked, once betting has closed and before it is settled.
+    pub fn set_selection_result(
+        env: Env,
+        operator: Address,
+        match_id: BytesN<32>,
+        bet_type: Symbol,
+        result: SelectionResult,
+    ) -> Result<(), ContractError> {
+        require_operator(&env, &operator)?;
+
+        if result == SelectionResult::Pending {
+            return Err(ContractError::InvalidStatus);
+        }
+        // Unrecorded selections on a settled match already count as lost
+        if matches::get_match(&env, &match_id)?.status != MatchStatus::Locked {
+            return Err(ContractError::InvalidStatus);
+        }
+        let key = DataKey::SelectionResult(match_id.clone(), bet_type.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(ContractError::BetAlreadySettled);
+        }
+        env.storage().persistent().set(&key, &result);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "selection_result"), match_id),
+            (bet_type, result),
+        );
+        Ok(())
+    }
+
+    /// Get the recorded result of a selection
+    pub fn get_selection_result(
+        env: Env,
+        match_id: BytesN<32>,
+        bet_type: Symbol,
+    ) -> SelectionResult {
+        accumulators::get_selection_result(&env, &match_id, &bet_type)
+    }
+
+    /// Update an accumulator's legs from the results of settled or voided
+    /// matches and settle it once a leg loses or every leg is decided. Anyone may call this; winnings
+    /// and refunds only go to the bettor.
+    pub fn resolve_accumulator(env: Env, acca_id: U256) -> Result<Accumulator, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
+        ensure_not_paused(&env, PauseScope::Withdrawals)?;
+        let mut acca = accumulators::get_accumulator(&env, &acca_id)?;
+        if acca.state != BetState::Active {
+            return Err(ContractError::BetAlreadySettled);
+        }
+
+        let liability = accumulators::potential_payout(&acca)?;
+        accumulators::resolve(&env, &mut acca)?;
+        accumulators::store_accumulator(&env, &acca);
+
+        if acca.state == BetState::Active {
+            return Ok(acca);
+        }
+        accumulators::release(&env, &acca, liability);
+
+        let mut user_accas = accumulators::get_user_accumulators(&env, &acca.bettor);
+        if let Some(index) = user_accas.first_index_of(&acca.acca_id) {
+            user_accas.remove(index);
+        }
+        accumulators::set_user_accumulators(&env, &acca.bettor, &user_accas);
+
+        responsible::record_result(&env, &acca.bettor, acca.amount, acca.payout);
+
+        if acca.payout > 0 {
+            let token_client = token::Client::new(&env, &acca.token);
+            token_client.transfer(&env.current_contract_address(), &acca.bettor, &acca.payout);
+        }
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "acca_settled"), acca_id),
+            (acca.state, acca.payout),
+        );
+
+        Ok(acca)
+    }
+
+    /// Get an accumulator by its bet ID
+    pub fn get_accumulator(env: Env, acca_id: U256) -> Result<Accumulator, ContractError> {
+        accumulators::get_accumulator(&env, &acca_id)
+    }
+
+    /// Get the IDs of a user's unsettled accumulators
+    pub fn get_user_accumulators(env: Env, user: Address) -> Vec<U256> {
+        accumulators::get_user_accumulators(&env, &user)
+    }
+
+    /// Cancel an active bet while its match is still open and refund the
+    /// stake, minus the admin-configured cancellation fee.
+    pub fn cancel_bet(env: Env, bettor: Address, bet_id: U256) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Withdrawals)?;
+        bettor.require_auth();
+
+        let mut bet = bets::get_bet(&env, &bet_id)?;
+        if bet.bettor != bettor {
+            return Err(ContractError::Unauthorized);
+        }
+        if bet.state != BetState::Active {
+            return Err(ContractError::InvalidStatus);
+        }
+        // A free bet has no escrowed stake to hand back
+        if freebets::voucher_of(&env, &bet_id).is_some() {
+            return Err(ContractError::InvalidBet);
+        }
+        matches::ensure_betting_open(&env, &bet.match_id)?;
+        claims::ensure_not_in_settlement(&env, &bet)?;
+
+        let fee_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CancellationFeeBps)
+            .unwrap_or(0);
+        let fee = bet
+            .amount
+            .checked_mul(fee_bps as i128)
+            .ok_or(ContractError::InvalidAmount)?
+            / MAX_FEE_BPS as i128;
+        let refund = bet.amount - fee;
+
+        bet.state = BetState::Cancelled;
+        bets::store_bet(&env, &bet);
+        bets::deactivate_bet(&env, &bet);
+
+        if fee > 0 {
+            let fees_key = DataKey::CollectedFees(bet.token.clone());
+            let collected: i128 = env.storage().persistent().get(&fees_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&fees_key, &(collected + fee));
+        }
+
+        let token_client = token::Client::new(&env, &bet.token);
+        token_client.transfer(&env.current_contract_address(), &bettor, &refund);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "bet_cancelled"), bet_id),
+            (bettor, refund, fee),
+        );
+
+        Ok(refund)
+    }
+
+    /// Configure the cancellation fee, in basis points of the stake
+    pub fn set_cancellation_fee(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+
+        if fee_bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::CancellationFeeBps, &fee_bps);
+        Ok(())
+    }
+
+    /// Get the cancellation fee, in basis points of the stake
+    pub fn get_cancellation_fee(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CancellationFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Get the cancellation fees retained in escrow for a token
+    pub fn get_collected_fees(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CollectedFees(token))
+            .unwrap_or(0)
+    }
+
+    /// Move the cancellation fees retained in escrow for a token to the
+    /// treasury. Returns the amount moved.
+    pub fn withdraw_fees(env: Env, admin: Address, token: Address) -> Result<i128, ContractError> {
+        require_admin(&env, &admin)?;
+        let treasury = claims::get_treasury(&env).ok_or(ContractError::NotInitialized)?;
+
+        let fees_key = DataKey::CollectedFees(token.clone());
+        let collected: i128 = env.storage().persistent().get(&fees_key).unwrap_or(0);
+        if collected > 0 {
+            env.storage().persistent().remove(&fees_key);
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&env.current_contract_address(), &treasury, &collected);
+        }
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "fees_withdrawn"), token),
+            (treasury, collected),
+        );
+        Ok(collected)
+    }
+
+    /// Accept a SEP-41 token for betting with the given stake bounds, or
+    /// update the bounds of an accepted token. `decimals` must match the
+    /// token's own.
+    pub fn set_token_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        min_stake: i128,
+        max_stake: i128,
+        decimals: u32,
+    ) -> Result<TokenConfig, ContractError> {
+        require_admin(&env, &admin)?;
+
+        if min_stake <= 0 || max_stake < min_stake {
+            return Err(ContractError::InvalidAmount);
+        }
+        if token::Client::new(&env, &token).decimals() != decimals {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let config = TokenConfig {
+            token: token.clone(),
+            min_stake,
+            max_stake,
+            decimals,
+        };
+        tokens::allow(&env, &config);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "token_allowed"), token),
+            (min_stake, max_stake, decimals),
+        );
+        Ok(config)
+    }
+
+    /// Stop accepting a token for new bets. Existing bets keep paying out in
+    /// the token they were placed with.
+    pub fn remove_token(env: Env, admin: Address, token: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        tokens::disallow(&env, &token)?;
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "token_removed"), token), ());
+        Ok(())
+    }
+
+    /// Get the configuration of an accepted token
+    pub fn get_token_config(env: Env, token: Address) -> Option<TokenConfig> {
+        tokens::get_config(&env, &token)
+    }
+
+    /// Get the tokens currently accepted for betting
+    pub fn get_allowed_tokens(env: Env) -> Vec<Address> {
+        tokens::allowed_tokens(&env)
+    }
+
+    /// Set the maximum potential payout accepted on a match, in total and
+    /// per selection. Zero leaves that liability uncapped.
+    pub fn set_liability_limits(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+        max_match_liability: i128,
+        max_selection_liability: i128,
+    ) -> Result<LiabilityLimits, ContractError> {
+        require_admin(&env, &admin)?;
+        matches::get_match(&env, &match_id)?;
+
+        if max_match_liability < 0 || max_selection_liability < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let limits = LiabilityLimits {
+            max_match_liability,
+            max_selection_liability,
+        };
+        exposure::set_limits(&env, &match_id, &limits);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "liability_limits"), match_id),
+            (max_match_liability, max_selection_liability),
+        );
+        Ok(limits)
+    }
+
+    /// Get the liability limits configured for a match
+    pub fn get_liability_limits(env: Env, match_id: BytesN<32>) -> LiabilityLimits {
+        exposure::get_limits(&env, &match_id)
+    }
+
+    /// Get the potential payout of all active bets on a match
+    pub fn get_match_exposure(env: Env, match_id: BytesN<32>) -> i128 {
+        exposure::match_exposure(&env, &match_id)
+    }
+
+    /// Get the potential payout of all active bets on one selection of a match
+    pub fn get_selection_exposure(env: Env, match_id: BytesN<32>, bet_type: Symbol) -> i128 {
+        exposure::selection_exposure(&env, &match_id, &bet_type)
+    }
+
+    /// Open a pari-mutuel pool on a match. Stakes on the listed outcomes
+    /// share one pot, paid to the winning outcome after `house_take_bps`.
+    pub fn create_pool(
+        env: Env,
+        operator: Address,
+        match_id: BytesN<32>,
+        token_address: Address,
+        outcomes: Vec<Symbol>,
+        house_take_bps: u32,
+    ) -> Result<Pool, ContractError> {
+        require_operator(&env, &operator)?;
+        matches::get_match(&env, &match_id)?;
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Pool(match_id.clone()))
+        {
+            return Err(ContractError::PoolAlreadyExists);
+        }
+        if tokens::get_config(&env, &token_address).is_none() {
+            return Err(ContractError::TokenNotAllowed);
+        }
+        if house_take_bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidAmount);
+        }
+        pools::validate_outcomes(&outcomes)?;
+
+        let pool = Pool {
+            match_id: match_id.clone(),
+            token: token_address,
+            outcomes,
+            house_take_bps,
+            total_staked: 0,
+            status: PoolStatus::Open,
+            winning_outcome: None,
+            winning_total: 0,
+            net_pot: 0,
+            claimed_stake: 0,
+            paid_out: 0,
+        };
+        pools::store_pool(&env, &pool);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "pool_created"), match_id),
+            (pool.token.clone(), house_take_bps),
+        );
+        Ok(pool)
+    }
+
+    /// Stake on an outcome of a match's pool while the match accepts bets
+    pub fn place_pool_bet(
+        env: Env,
+        bettor: Address,
+        match_id: BytesN<32>,
+        outcome: Symbol,
+        amount: i128,
+    ) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Bets)?;
+        bettor.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut pool = pools::get_pool(&env, &match_id)?;
+        tokens::ensure_stake_allowed(&env, &pool.token, amount)?;
+        matches::ensure_betting_open(&env, &match_id)?;
+        responsible::record_wager(&env, &bettor, amount, amount)?;
+
+        pools::add_stake(&env, &mut pool, &outcome, &bettor, amount)?;
+
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&bettor, env.current_contract_address(), &amount);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "pool_bet"), match_id.clone()),
+            (bettor.clone(), outcome.clone(), amount),
+        );
+        Ok(pools::stake_of(&env, &match_id, &outcome, &bettor))
+    }
+
+    /// Settle a match's pool on its winning outcome
+    pub fn settle_pool(
+        env: Env,
+        operator: Address,
+        match_id: BytesN<32>,
+        winning_outcome: Symbol,
+    ) -> Result<Pool, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
+        require_operator(&env, &operator)?;
+
+        let mut pool = pools::get_pool(&env, &match_id)?;
+        pools::settle(&env, &mut pool, &winning_outcome)?;
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "pool_settled"), match_id),
+            (winning_outcome, pool.status, pool.net_pot),
+        );
+        Ok(pool)
+    }
+
+    /// Claim a share of a settled pool, or a refund from a void pool.
+    /// Returns the amount paid.
+    pub fn claim_pool(
+        env: Env,
+        bettor: Address,
+        match_id: BytesN<32>,
+    ) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Withdrawals)?;
+        bettor.require_auth();
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct SpinExecution {
-    pub spin_id: BytesN<32>,
-    pub executor: Address,
-    pub timestamp: u64,
-}
+        let mut pool = pools::get_pool(&env, &match_id)?;
+        let amount = pools::claim(&env, &mut pool, &bettor)?;
 
-#[contracttype]
-#[derive(Clone)]
-pub struct Bet {
-    pub bettor: Address,
-    pub amount: i128,
-    pub match_id: BytesN<32>,
-    pub bet_type: Symbol,
-    pub odds: u32,
-    pub timestamp: u64,
-}
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "pool_claimed"), match_id),
+            (bettor, amount),
+        );
+        Ok(amount)
+    }
 
-#[contracttype]
-pub enum DataKey {
-    BackendSigner,
-    UsedSpinHashes,
-    SpinExecutions,
-    Bet(BytesN<32>, Address),
-    PreventDoubleBetting,
-}
+    /// Count a bettor's losing stakes on a settled pool toward their loss
+    /// limit. Anyone may call this, since losing stakes are never claimed.
+    /// Returns the amount recorded.
+    pub fn record_pool_loss(
+        env: Env,
+        match_id: BytesN<32>,
+        bettor: Address,
+    ) -> Result<i128, ContractError> {
+        let pool = pools::get_pool(&env, &match_id)?;
+        let lost = pools::record_loss(&env, &pool, &bettor)?;
 
-#[contract]
-pub struct BettingContract;
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "pool_loss"), match_id), (bettor, lost));
+        Ok(lost)
+    }
 
-#[contractimpl]
-impl BettingContract {
-    /// Initialize the contract with the backend signer address
-    pub fn initialize(env: Env, backend_signer: Address) {
-        let storage = env.storage().persistent();
-        storage.set(&DataKey::BackendSigner, &backend_signer);
+    /// Get a match's pool
+    pub fn get_pool(env: Env, match_id: BytesN<32>) -> Result<Pool, ContractError> {
+        pools::get_pool(&env, &match_id)
     }
 
-    /// Place a bet and escrow funds
-    pub fn place_bet(
+    /// Get the total staked on an outcome of a pool
+    pub fn get_pool_outcome_total(env: Env, match_id: BytesN<32>, outcome: Symbol) -> i128 {
+        pools::outcome_total(&env, &match_id, &outcome)
+    }
+
+    /// Get a bettor's stake on an outcome of a pool
+    pub fn get_pool_stake(
         env: Env,
+        match_id: BytesN<32>,
+        outcome: Symbol,
         bettor: Address,
-        token_address: Address,
-        amount: i128,
+    ) -> i128 {
+        pools::stake_of(&env, &match_id, &outcome, &bettor)
+    }
+
+    /// Get the current implied odds of a pool outcome, scaled by 100
+    pub fn get_pool_odds(
+        env: Env,
         match_id: BytesN<32>,
-        bet_type: Symbol,
-        odds: u32,
+        outcome: Symbol,
+    ) -> Result<i128, ContractError> {
+        let pool = pools::get_pool(&env, &match_id)?;
+        Ok(pools::implied_odds(&env, &pool, &outcome))
+    }
+
+    /// Set a user's wager and loss caps. Tighter caps apply immediately;
+    /// looser ones are queued and returned as pending until the cooling-off
+    /// delay has passed.
+    pub fn set_gaming_limits(
+        env: Env,
+        user: Address,
+        limits: GamingLimits,
+    ) -> Result<Option<PendingLimits>, ContractError> {
+        user.require_auth();
+        let pending = responsible::set_limits(&env, &user, &limits)?;
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "gaming_limits"), user.clone()),
+            (responsible::get_limits(&env, &user), pending.clone()),
+        );
+        Ok(pending)
+    }
+
+    /// Get the wager and loss caps currently in force for a user
+    pub fn get_gaming_limits(env: Env, user: Address) -> GamingLimits {
+        responsible::get_limits(&env, &user)
+    }
+
+    /// Get a user's loosened caps waiting out the cooling-off delay
+    pub fn get_pending_gaming_limits(env: Env, user: Address) -> Option<PendingLimits> {
+        responsible::get_pending(&env, &user)
+            .filter(|pending| env.ledger().timestamp() < pending.effective_at)
+    }
+
+    /// Get a user's staked and lost amounts in the current windows
+    pub fn get_wager_totals(env: Env, user: Address) -> WagerTotals {
+        responsible::get_totals(&env, &user)
+    }
+
+    /// Exclude the caller from betting and spins for `duration_seconds`.
+    /// Returns the time the exclusion ends; it cannot be shortened.
+    pub fn self_exclude(
+        env: Env,
+        user: Address,
+        duration_seconds: u64,
+    ) -> Result<u64, ContractError> {
+        user.require_auth();
+
+        if duration_seconds == 0 {
+            return Err(ContractError::InvalidTimeWindow);
+        }
+        let until = env
+            .ledger()
+            .timestamp()
+            .checked_add(duration_seconds)
+            .ok_or(ContractError::InvalidTimeWindow)?;
+        let until = responsible::exclude(&env, &user, until);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "self_excluded"), user), until);
+        Ok(until)
+    }
+
+    /// Get the time a user's self-exclusion ends, or zero
+    pub fn get_self_exclusion(env: Env, user: Address) -> u64 {
+        responsible::excluded_until(&env, &user)
+    }
+
+    /// Set the guardian allowed to pause the contract. The guardian must not
+    /// be the admin or operator, so a leaked backend account cannot lift a
+    /// pause.
+    pub fn set_guardian(env: Env, admin: Address, guardian: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        let operator: Option<Address> = env.storage().persistent().get(&DataKey::Operator);
+        if guardian == admin || Some(guardian.clone()) == operator {
+            return Err(ContractError::InvalidGuardian);
+        }
+        common::set_guardian(&env, &guardian);
+        Ok(())
+    }
+
+    /// Get the guardian allowed to pause the contract
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        common::get_guardian(&env)
+    }
+
+    /// Pause one area of the contract
+    pub fn pause(env: Env, guardian: Address, scope: PauseScope) -> Result<(), ContractError> {
+        common::require_guardian(&env, &guardian)?;
+        common::set_paused(&env, scope, true);
+        Ok(())
+    }
+
+    /// Resume a paused area of the contract
+    pub fn unpause(env: Env, guardian: Address, scope: PauseScope) -> Result<(), ContractError> {
+        common::require_guardian(&env, &guardian)?;
+        common::set_paused(&env, scope, false);
+        Ok(())
+    }
+
+    /// Check whether an area of the contract is paused
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        common::is_paused(&env, scope)
+    }
+
+    /// Set the treasury that receives the stakes of lost bets
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Treasury, &treasury);
+        Ok(())
+    }
+
+    /// Get the treasury that receives the stakes of lost bets
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        claims::get_treasury(&env)
+    }
+
+    /// Set the settlement contract allowed to mark bets settled. Once set,
+    /// bets it is settling can no longer be claimed, cancelled or cashed
+    /// out here.
+    pub fn set_settlement_contract(
+        env: Env,
+        admin: Address,
+        settlement: Address,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::SettlementContract, &settlement);
+        Ok(())
+    }
+
+    /// Get the settlement contract allowed to mark bets settled
+    pub fn get_settlement_contract(env: Env) -> Option<Address> {
+        claims::get_settlement_contract(&env)
+    }
+
+    /// Record the state bets were settled to by the settlement contract,
+    /// which pays them through the balance ledger. Active bets are closed,
+    /// their escrowed stake moves to the treasury and they count toward the
+    /// bettor's loss limit with `payout` as the return; bets already settled
+    /// only have their state corrected.
+    pub fn mark_settled(
+        env: Env,
+        settlement: Address,
+        results: Vec<(U256, BetState, i128)>,
     ) -> Result<(), ContractError> {
+        require_role(&env, &DataKey::SettlementContract, &settlement)?;
+
+        for (bet_id, state, payout) in results.iter() {
+            let mut bet = bets::get_bet(&env, &bet_id)?;
+            if !matches!(state, BetState::Won | BetState::Lost | BetState::Void) {
+                return Err(ContractError::InvalidStatus);
+            }
+            match bet.state {
+                BetState::Active => {
+                    bet.state = state;
+                    bets::store_bet(&env, &bet);
+                    bets::deactivate_bet(&env, &bet);
+                    let stake = claims::release_to_treasury(&env, &bet)?;
+                    responsible::record_result(&env, &bet.bettor, stake, payout);
+                }
+                BetState::Won | BetState::Lost | BetState::Void => {
+                    bet.state = state;
+                    bets::store_bet(&env, &bet);
+                }
+                _ => return Err(ContractError::BetAlreadySettled),
+            }
+
+            #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+            env.events()
+                .publish((Symbol::new(&env, "bet_settled"), bet_id), (state, payout));
+        }
+        Ok(())
+    }
+
+    /// Claim a bet once its match is settled. A won bet pays stake x odds
+    /// and a voided selection refunds the stake, in the bet's token, from
+    /// escrow and the house bankroll. Returns the amount paid.
+    pub fn claim(env: Env, bettor: Address, bet_id: U256) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Withdrawals)?;
         bettor.require_auth();
 
-        if amount <= 0 {
+        let mut bet = bets::get_bet(&env, &bet_id)?;
+        if bet.bettor != bettor {
+            return Err(ContractError::Unauthorized);
+        }
+        claims::claim(&env, &mut bet)
+    }
+
+    /// Build the domain-separated payload the backend signs for a cash-out
+    /// quote
+    pub fn cash_out_signing_payload(env: Env, quote: CashOutQuote) -> Bytes {
+        cashout::quote_payload(&env, &quote)
+    }
+
+    /// Close an open bet early at a backend-quoted value, paid in the bet's
+    /// token. The quote is signed by the backend key, expires at
+    /// `quote.expires_at`, and its nonce can only be used once. Returns the
+    /// amount paid.
+    pub fn cash_out(
+        env: Env,
+        bettor: Address,
+        quote: CashOutQuote,
+        signature: BytesN<64>,
+    ) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Withdrawals)?;
+        bettor.require_auth();
+
+        let mut bet = bets::get_bet(&env, &quote.bet_id)?;
+        if bet.bettor != bettor {
+            return Err(ContractError::Unauthorized);
+        }
+        if bet.state != BetState::Active {
+            return Err(ContractError::BetAlreadySettled);
+        }
+        let record = matches::get_match(&env, &bet.match_id)?;
+        if record.status != MatchStatus::Open && record.status != MatchStatus::Locked {
+            return Err(ContractError::InvalidStatus);
+        }
+        if quote.amount <= 0 {
             return Err(ContractError::InvalidAmount);
         }
+        claims::ensure_not_in_settlement(&env, &bet)?;
 
-        let storage = env.storage().persistent();
+        let public_key: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BackendPublicKey)
+            .ok_or(ContractError::InvalidSignature)?;
+        if env.ledger().timestamp() > quote.expires_at {
+            return Err(ContractError::SignatureExpired);
+        }
+        let payload = cashout::quote_payload(&env, &quote);
+        env.crypto()
+            .ed25519_verify(&public_key, &payload, &signature);
 
-        // Check if double betting is prevented
-        let prevent_double: bool = storage.get(&DataKey::PreventDoubleBetting).unwrap_or(false);
-        if prevent_double {
-            if storage.has(&DataKey::Bet(match_id.clone(), bettor.clone())) {
-                return Err(ContractError::BetAlreadyPlaced);
-            }
+        ensure_not_replayed(
+            &env,
+            Symbol::new(&env, "cash_out"),
+            quote.nonce.clone(),
+            None,
+        )?;
+
+        let token_client = token::Client::new(&env, &bet.token);
+        if token_client.balance(&env.current_contract_address()) < quote.amount {
+            return Err(ContractError::InsufficientBalance);
         }
 
-        // Lock funds (transfer from bettor to contract)
-        let token_client = token::Client::new(&env, &token_address);
-        token_client.transfer(&bettor, &env.current_contract_address(), &amount);
+        bet.state = BetState::CashedOut;
+        bets::store_bet(&env, &bet);
+        bets::deactivate_bet(&env, &bet);
+        let stake = bet.funded_stake();
+        responsible::record_result(&env, &bettor, stake, quote.amount);
 
-        // Store bet
-        let timestamp = env.ledger().timestamp();
-        let bet = Bet {
-            bettor: bettor.clone(),
-            amount,
-            match_id: match_id.clone(),
-            bet_type: bet_type.clone(),
-            odds,
-            timestamp,
-        };
+        token_client.transfer(&env.current_contract_address(), &bettor, &quote.amount);
 
-        storage.set(&DataKey::Bet(match_id.clone(), bettor.clone()), &bet);
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "bet_cashed_out"), quote.bet_id),
+            (bettor, bet.token, quote.amount),
+        );
+        Ok(quote.amount)
+    }
 
-        // Emit bet placed event
-        let event = BetPlacedEvent {
-            bettor: bettor.clone(),
-            bet_id: Symbol::new(&env, "bet"), // Using a generic symbol or match_id as symbol
-            amount,
-        };
-        // Note: The common::BetPlacedEvent uses Symbol for bet_id.
-        // We might want to emit a more detailed event or use Match ID.
-        // For now, let's satisfy the criteria with what's available.
-        env.events()
-            .publish((Symbol::new(&env, "bet_placed"), match_id.clone()), event);
+    /// Sweep the stakes of lost bets on a settled match to the treasury in
+    /// batches. Anyone may call this. Returns the number of bets on the match
+    /// still to be scanned.
+    pub fn sweep_losing_stakes(
+        env: Env,
+        match_id: BytesN<32>,
+        batch_size: u32,
+    ) -> Result<u32, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
+        claims::sweep_batch(&env, &match_id, batch_size)
+    }
 
-        Ok(())
+    /// Get a bet by its ID
+    pub fn get_bet(env: Env, bet_id: U256) -> Result<Bet, ContractError> {
+        bets::get_bet(&env, &bet_id)
+    }
+
+    /// Get the IDs of a user's active bets
+    pub fn get_user_bets(env: Env, user: Address) -> Vec<U256> {
+        bets::get_user_active_bets(&env, &user)
+    }
+
+    /// Get the number of bets placed on a match
+    pub fn get_match_bet_count(env: Env, match_id: BytesN<32>) -> u32 {
+        bets::match_bet_count(&env, &match_id)
+    }
+
+    /// Get a page of bet IDs placed on a match, in placement order
+    pub fn get_match_bets(env: Env, match_id: BytesN<32>, start: u32, limit: u32) -> Vec<U256> {
+        let storage = env.storage().persistent();
+        let end = bets::match_bet_count(&env, &match_id).min(start.saturating_add(limit));
+        let mut bet_ids = Vec::new(&env);
+        for index in start..end {
+            if let Some(bet_id) = storage.get(&DataKey::MatchBet(match_id.clone(), index)) {
+                bet_ids.push_back(bet_id);
+            }
+        }
+        bet_ids
     }
 
     /// Configure double betting prevention
@@ -108,19 +1227,11 @@ impl BettingContract {
         admin: Address,
         prevent: bool,
     ) -> Result<(), ContractError> {
-        // Only backend signer (acting as admin) can change settings
-        // In a real scenario, you'd have a separate admin role
-        let storage = env.storage().persistent();
-        let backend_signer: Address = storage
-            .get(&DataKey::BackendSigner)
-            .ok_or(ContractError::Unauthorized)?;
+        require_admin(&env, &admin)?;
 
-        admin.require_auth();
-        if admin != backend_signer {
-            return Err(ContractError::Unauthorized);
-        }
-
-        storage.set(&DataKey::PreventDoubleBetting, &prevent);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PreventDoubleBetting, &prevent);
         Ok(())
     }
 
@@ -132,47 +1243,237 @@ impl BettingContract {
             .unwrap_or(false)
     }
 
+    /// Keep emitting the legacy `bet_placed` event alongside the typed
+    /// `BetEvent`. On by default while listeners move to the new event; the
+    /// legacy event will be removed in the next release.
+    pub fn set_legacy_bet_events(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::LegacyBetEvents, &enabled);
+        Ok(())
+    }
+
+    /// Check if the legacy `bet_placed` event is still emitted
+    pub fn is_legacy_bet_events_enabled(env: Env) -> bool {
+        legacy_bet_events_enabled(&env)
+    }
+
+    /// Register a match with its betting window. Bets are accepted once the
+    /// match is opened and the ledger time is in `[open_time, lock_time)`.
+    pub fn create_match(
+        env: Env,
+        operator: Address,
+        match_id: BytesN<32>,
+        open_time: u64,
+        lock_time: u64,
+    ) -> Result<Match, ContractError> {
+        require_operator(&env, &operator)?;
+
+        if lock_time <= open_time {
+            return Err(ContractError::InvalidTimeWindow);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Match(match_id.clone()))
+        {
+            return Err(ContractError::MatchAlreadyExists);
+        }
+
+        let record = Match {
+            match_id,
+            open_time,
+            lock_time,
+            status: MatchStatus::Scheduled,
+            created_at: env.ledger().timestamp(),
+        };
+        matches::store_match(&env, &record);
+        matches::publish_match_status(&env, &record);
+
+        Ok(record)
+    }
+
+    /// Move a match to its next lifecycle state. Voiding goes through
+    /// `void_match` so that escrowed stakes are refunded.
+    pub fn update_match_status(
+        env: Env,
+        operator: Address,
+        match_id: BytesN<32>,
+        status: MatchStatus,
+    ) -> Result<Match, ContractError> {
+        require_operator(&env, &operator)?;
+
+        let mut record = matches::get_match(&env, &match_id)?;
+        if status == MatchStatus::Void || !matches::is_valid_transition(record.status, status) {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        record.status = status;
+        matches::store_match(&env, &record);
+        matches::publish_match_status(&env, &record);
+
+        Ok(record)
+    }
+
+    /// Void a postponed or abandoned match and refund the first batch of
+    /// its escrowed bets. Remaining bets are refunded through
+    /// `process_void_refunds`.
+    pub fn void_match(
+        env: Env,
+        operator: Address,
+        match_id: BytesN<32>,
+        batch_size: u32,
+    ) -> Result<VoidProgress, ContractError> {
+        ensure_not_paused(&env, PauseScope::Withdrawals)?;
+        require_operator(&env, &operator)?;
+
+        let mut record = matches::get_match(&env, &match_id)?;
+        if !matches::is_valid_transition(record.status, MatchStatus::Void) {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        record.status = MatchStatus::Void;
+        matches::store_match(&env, &record);
+        matches::publish_match_status(&env, &record);
+
+        refunds::start(&env, &match_id);
+        refunds::process_batch(&env, &match_id, batch_size)
+    }
+
+    /// Refund the next batch of bets on a voided match. Anyone may call
+    /// this, since stakes are only ever returned to their bettors.
+    pub fn process_void_refunds(
+        env: Env,
+        match_id: BytesN<32>,
+        batch_size: u32,
+    ) -> Result<VoidProgress, ContractError> {
+        ensure_not_paused(&env, PauseScope::Withdrawals)?;
+        refunds::process_batch(&env, &match_id, batch_size)
+    }
+
+    /// Get the refund progress of a voided match
+    pub fn get_void_progress(env: Env, match_id: BytesN<32>) -> Option<VoidProgress> {
+        refunds::get_progress(&env, &match_id)
+    }
+
+    /// Get a registered match
+    pub fn get_match(env: Env, match_id: BytesN<32>) -> Result<Match, ContractError> {
+        matches::get_match(&env, &match_id)
+    }
+
+    /// Check whether a match is currently accepting bets
+    pub fn is_betting_open(env: Env, match_id: BytesN<32>) -> bool {
+        matches::ensure_betting_open(&env, &match_id).is_ok()
+    }
+
+    /// Register or rotate the ed25519 public key the backend signs spins,
+    /// cash-out quotes and odds with. Signatures from the previous key are
+    /// rejected from this call on.
+    pub fn rotate_backend_signer(
+        env: Env,
+        admin: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+
+        let storage = env.storage().persistent();
+        let previous: Option<BytesN<32>> = storage.get(&DataKey::BackendPublicKey);
+        storage.set(&DataKey::BackendPublicKey, &public_key);
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "signer_rotated"),),
+            (previous, public_key),
+        );
+        Ok(())
+    }
+
+    /// Get the ed25519 public key used to verify backend-signed payloads
+    pub fn get_backend_public_key(env: Env) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::BackendPublicKey)
+    }
+
+    /// Build the domain-separated payload the backend signs for a spin
+    pub fn spin_signing_payload(
+        env: Env,
+        spin_id: BytesN<32>,
+        spin_hash: BytesN<32>,
+        executor: Address,
+        expires_at: u64,
+    ) -> Bytes {
+        spin_payload(&env, &spin_id, &spin_hash, &executor, expires_at)
+    }
+
     /// Execute a spin with backend signature verification
     ///
     /// # Arguments
     /// * `spin_id` - Unique identifier for the spin (32-byte hash)
     /// * `spin_hash` - Hash of spin parameters for replay protection
-    /// * `signature` - Signature from backend signer
+    /// * `signature` - ed25519 signature from the backend over the spin payload
     /// * `executor` - Address executing the spin
+    /// * `expires_at` - Ledger timestamp after which the signature is no longer accepted
     ///
     /// # Returns
-    /// `Result<(), ContractError>`
-    /// Execute a spin with backend authorization
+    /// `Result<(), ContractError>`. `InvalidSignature` is only returned when
+    /// no backend key is registered; see `execute_spin_with_ttl` for a
+    /// signature that does not verify.
     pub fn execute_spin(
         env: Env,
         spin_id: BytesN<32>,
         spin_hash: BytesN<32>,
         signature: BytesN<64>,
         executor: Address,
+        expires_at: u64,
     ) -> Result<(), ContractError> {
-        Self::execute_spin_with_ttl(env, spin_id, spin_hash, signature, executor, None)
+        Self::execute_spin_with_ttl(
+            env, spin_id, spin_hash, signature, executor, expires_at, None,
+        )
     }
 
+    /// Execute a spin with backend signature verification and an optional
+    /// replay-protection TTL for the spin hash.
+    ///
+    /// The signature covers the contract ID, `spin_id`, `spin_hash`, executor
+    /// and expiry, so the executor can submit a backend-signed voucher without
+    /// the backend co-signing the transaction.
+    ///
+    /// A signature that does not verify against the registered key cannot be
+    /// reported as `InvalidSignature`: soroban-sdk only offers a trapping
+    /// `ed25519_verify`, so the host aborts the invocation and the caller sees
+    /// a failed transaction without a contract error code. Nothing is stored
+    /// in that case.
     pub fn execute_spin_with_ttl(
         env: Env,
         spin_id: BytesN<32>,
         spin_hash: BytesN<32>,
         signature: BytesN<64>,
         executor: Address,
+        expires_at: u64,
         ttl_seconds: Option<u64>,
     ) -> Result<(), ContractError> {
+        ensure_not_paused(&env, PauseScope::Spins)?;
         executor.require_auth();
+        responsible::ensure_not_excluded(&env, &executor)?;
 
         let storage = env.storage().persistent();
 
-        // Get backend signer
-        let backend_signer: Address = storage
-            .get(&DataKey::BackendSigner)
-            .ok_or(ContractError::Unauthorized)?;
+        // Get backend public key
+        let public_key: BytesN<32> = storage
+            .get(&DataKey::BackendPublicKey)
+            .ok_or(ContractError::InvalidSignature)?;
+
+        if env.ledger().timestamp() > expires_at {
+            return Err(ContractError::SignatureExpired);
+        }
 
-        // Signature verification hook; auth is currently enforced via backend signer auth.
-        let _ = signature;
-        backend_signer.require_auth();
+        let payload = spin_payload(&env, &spin_id, &spin_hash, &executor, expires_at);
+        env.crypto()
+            .ed25519_verify(&public_key, &payload, &signature);
 
         ensure_not_replayed(
             &env,
@@ -181,13 +1482,8 @@ impl BettingContract {
             ttl_seconds,
         )?;
 
-        // Store spin execution
-        let executions: Map<BytesN<32>, SpinExecution> = storage
-            .get(&DataKey::SpinExecutions)
-            .unwrap_or_else(|| Map::new(&env));
-
         // Check for duplicate execution on spin ID
-        if executions.get(spin_id.clone()).is_some() {
+        if spins::get_execution(&env, &spin_id).is_some() {
             return Err(ContractError::SpinAlreadyExecuted);
         }
 
@@ -198,11 +1494,8 @@ impl BettingContract {
             executor: executor.clone(),
             timestamp: current_time,
         };
+        spins::store_execution(&env, &execution);
 
-        // Update storage
-        let mut new_executions = executions.clone();
-        new_executions.set(spin_id.clone(), execution.clone());
-        storage.set(&DataKey::SpinExecutions, &new_executions);
         // Emit execution event
         let event = SpinExecutedEvent {
             spin_id: spin_id.clone(),
@@ -210,6 +1503,7 @@ impl BettingContract {
             timestamp: current_time,
         };
 
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
         env.events()
             .publish((Symbol::new(&env, "spin_executed"),), event);
 
@@ -218,12 +1512,7 @@ impl BettingContract {
 
     /// Check if a spin has already been executed
     pub fn is_spin_executed(env: Env, spin_id: BytesN<32>) -> bool {
-        let storage = env.storage().persistent();
-        let executions: Map<BytesN<32>, SpinExecution> = storage
-            .get(&DataKey::SpinExecutions)
-            .unwrap_or_else(|| Map::new(&env));
-
-        executions.get(spin_id).is_some()
+        spins::get_execution(&env, &spin_id).is_some()
     }
 
     /// Get spin execution details
@@ -231,12 +1520,19 @@ impl BettingContract {
         env: Env,
         spin_id: BytesN<32>,
     ) -> Result<SpinExecution, ContractError> {
-        let storage = env.storage().persistent();
-        let executions: Map<BytesN<32>, SpinExecution> = storage
-            .get(&DataKey::SpinExecutions)
-            .unwrap_or_else(|| Map::new(&env));
+        spins::get_execution(&env, &spin_id).ok_or(ContractError::SpinNotFound)
+    }
 
-        executions.get(spin_id).ok_or(ContractError::SpinNotFound)
+    /// Move up to `batch_size` spin executions from the legacy single-map storage
+    /// to per-spin keys. Returns the number of executions still to migrate;
+    /// call again until it reaches zero.
+    pub fn migrate_spin_executions(
+        env: Env,
+        admin: Address,
+        batch_size: u32,
+    ) -> Result<u32, ContractError> {
+        require_admin(&env, &admin)?;
+        Ok(spins::migrate_legacy(&env, batch_size))
     }
 
     pub fn is_spin_hash_used(env: Env, spin_hash: BytesN<32>) -> bool {
@@ -247,5 +1543,140 @@ impl BettingContract {
         cleanup_operation(&env, Symbol::new(&env, "spin_exec"), spin_hash)
     }
 }
+
+/// Validate and record a single bet at already-resolved odds. The caller
+/// escrows the stake.
+#[allow(clippy::too_many_arguments)]
+fn record_bet(
+    env: &Env,
+    bettor: &Address,
+    token_address: &Address,
+    amount: i128,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+    odds: u32,
+    free_bet: bool,
+) -> Result<U256, ContractError> {
+    if amount <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    tokens::ensure_stake_allowed(env, token_address, amount)?;
+    matches::ensure_betting_open(env, match_id)?;
+    // A free bet's stake is not the bettor's to lose
+    let at_risk = if free_bet { 0 } else { amount };
+    responsible::record_wager(env, bettor, amount, at_risk)?;
+
+    let storage = env.storage().persistent();
+
+    // Double betting prevention is an optional policy on top of unique bet IDs
+    let prevent_double: bool = storage.get(&DataKey::PreventDoubleBetting).unwrap_or(false);
+    if prevent_double && bets::bettor_match_bet_count(env, match_id, bettor) > 0 {
+        return Err(ContractError::BetAlreadyPlaced);
+    }
+
+    let bet_id = bets::next_bet_id(env);
+    let timestamp = env.ledger().timestamp();
+    let bet = Bet {
+        bet_id: bet_id.clone(),
+        bettor: bettor.clone(),
+        token: token_address.clone(),
+        amount,
+        match_id: match_id.clone(),
+        bet_type: bet_type.clone(),
+        odds,
+        timestamp,
+        state: BetState::Active,
+        free_bet,
+    };
+
+    // Reject the stake if its potential payout breaches a liability limit
+    exposure::reserve(env, &bet)?;
+
+    // Store bet under its own ID and index it by user and match
+    bets::store_bet(env, &bet);
+    bets::index_bet(env, &bet);
+
+    // Emit bet placed event
+    let mut event = create_bet_event(
+        env,
+        bettor.clone(),
+        amount,
+        token_address.clone(),
+        bet_id.clone(),
+        env.current_contract_address(),
+        bet_type.clone(),
+        odds,
+    );
+    event.timestamp = timestamp;
+    if let Some(publication) = odds::get_current(env, match_id, bet_type) {
+        event.metadata.set(
+            Symbol::new(env, "odds_version"),
+            odds::version_string(env, publication.version),
+        );
+    }
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events()
+        .publish((BET_EVENT, match_id.clone(), bettor.clone()), event);
+
+    if legacy_bet_events_enabled(env) {
+        let legacy = BetPlacedEvent {
+            bettor: bettor.clone(),
+            bet_id: Symbol::new(env, "bet"),
+            amount,
+        };
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(env, "bet_placed"), match_id.clone()), legacy);
+    }
+
+    Ok(bet_id)
+}
+
+fn legacy_bet_events_enabled(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LegacyBetEvents)
+        .unwrap_or(true)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+    require_role(env, &DataKey::Admin, admin)
+}
+
+fn require_operator(env: &Env, operator: &Address) -> Result<(), ContractError> {
+    require_role(env, &DataKey::Operator, operator)
+}
+
+fn require_role(env: &Env, role: &DataKey, caller: &Address) -> Result<(), ContractError> {
+    let holder: Address = env
+        .storage()
+        .persistent()
+        .get(role)
+        .ok_or(ContractError::NotInitialized)?;
+
+    caller.require_auth();
+    if *caller != holder {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn spin_payload(
+    env: &Env,
+    spin_id: &BytesN<32>,
+    spin_hash: &BytesN<32>,
+    executor: &Address,
+    expires_at: u64,
+) -> Bytes {
+    let mut payload = Bytes::from_slice(env, SPIN_SIGNATURE_DOMAIN);
+    payload.append(&env.current_contract_address().to_xdr(env));
+    payload.append(&Bytes::from_array(env, &spin_id.to_array()));
+    payload.append(&Bytes::from_array(env, &spin_hash.to_array()));
+    payload.append(&executor.clone().to_xdr(env));
+    payload.extend_from_array(&expires_at.to_be_bytes());
+    payload
+}
+
 #[cfg(test)]
 mod test;
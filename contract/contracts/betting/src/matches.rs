@@ -0,0 +1,55 @@
+use common::ContractError;
+use soroban_sdk::{BytesN, Env, Symbol};
+
+use crate::DataKey;
+
+pub use common::{Match, MatchStatus};
+
+pub fn get_match(env: &Env, match_id: &BytesN<32>) -> Result<Match, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Match(match_id.clone()))
+        .ok_or(ContractError::MatchNotFound)
+}
+
+pub fn store_match(env: &Env, record: &Match) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Match(record.match_id.clone()), record);
+}
+
+pub fn is_valid_transition(from: MatchStatus, to: MatchStatus) -> bool {
+    matches!(
+        (from, to),
+        (MatchStatus::Scheduled, MatchStatus::Open)
+            | (MatchStatus::Open, MatchStatus::Locked)
+            | (MatchStatus::Locked, MatchStatus::Settled)
+            | (MatchStatus::Scheduled, MatchStatus::Void)
+            | (MatchStatus::Open, MatchStatus::Void)
+            | (MatchStatus::Locked, MatchStatus::Void)
+    )
+}
+
+/// Ensure the match exists and is currently accepting bets.
+///
+/// A match accepts bets only while it is `Open` and the ledger time is
+/// inside its `[open_time, lock_time)` window, so bets are refused after
+/// kickoff even if the admin has not locked the match yet.
+pub fn ensure_betting_open(env: &Env, match_id: &BytesN<32>) -> Result<Match, ContractError> {
+    let record = get_match(env, match_id)?;
+    let now = env.ledger().timestamp();
+
+    if record.status != MatchStatus::Open || now < record.open_time || now >= record.lock_time {
+        return Err(ContractError::BettingClosed);
+    }
+
+    Ok(record)
+}
+
+pub fn publish_match_status(env: &Env, record: &Match) {
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events().publish(
+        (Symbol::new(env, "match_status"), record.match_id.clone()),
+        (record.status, env.ledger().timestamp()),
+    );
+}
@@ -0,0 +1,135 @@
+use common::ContractError;
+use soroban_sdk::{contracttype, xdr::ToXdr, Bytes, BytesN, Env, String, Symbol};
+
+use crate::DataKey;
+
+/// Domain tag prepended to every backend-signed odds publication
+const ODDS_SIGNATURE_DOMAIN: &[u8] = b"renaissance:odds:v1";
+
+/// Default maximum age of an odds publication, in seconds
+pub const DEFAULT_ODDS_STALENESS: u64 = 30;
+
+const BPS_SCALE: u64 = 10_000;
+
+/// Backend-published price of a selection. Versions only move forward, so
+/// an older publication can never replace a newer one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OddsPublication {
+    pub match_id: BytesN<32>,
+    pub bet_type: Symbol,
+    pub odds: u32,
+    pub version: u64,
+    pub published_at: u64,
+}
+
+pub fn publication_payload(env: &Env, publication: &OddsPublication) -> Bytes {
+    let mut payload = Bytes::from_slice(env, ODDS_SIGNATURE_DOMAIN);
+    payload.append(&env.current_contract_address().to_xdr(env));
+    payload.append(&Bytes::from_array(env, &publication.match_id.to_array()));
+    payload.append(&publication.bet_type.clone().to_xdr(env));
+    payload.extend_from_array(&publication.odds.to_be_bytes());
+    payload.extend_from_array(&publication.version.to_be_bytes());
+    payload.extend_from_array(&publication.published_at.to_be_bytes());
+    payload
+}
+
+pub fn staleness_window(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OddsStaleness)
+        .unwrap_or(DEFAULT_ODDS_STALENESS)
+}
+
+pub fn get_current(env: &Env, match_id: &BytesN<32>, bet_type: &Symbol) -> Option<OddsPublication> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LiveOdds(match_id.clone(), bet_type.clone()))
+}
+
+fn ensure_fresh(env: &Env, publication: &OddsPublication) -> Result<(), ContractError> {
+    let now = env.ledger().timestamp();
+    if publication.published_at > now || now - publication.published_at > staleness_window(env) {
+        return Err(ContractError::OddsStale);
+    }
+    Ok(())
+}
+
+/// Store a publication as the current price of its selection. The
+/// signature must already have been checked.
+pub fn publish(env: &Env, publication: &OddsPublication) -> Result<(), ContractError> {
+    if publication.odds == 0 {
+        return Err(ContractError::InvalidBet);
+    }
+    ensure_fresh(env, publication)?;
+    if let Some(current) = get_current(env, &publication.match_id, &publication.bet_type) {
+        if publication.version <= current.version {
+            return Err(ContractError::OddsStale);
+        }
+    }
+    env.storage().persistent().set(
+        &DataKey::LiveOdds(publication.match_id.clone(), publication.bet_type.clone()),
+        publication,
+    );
+    Ok(())
+}
+
+/// Price a pre-match bet on a selection at the odds the house currently
+/// offers. A pre-match price stays valid until the backend publishes a new
+/// version. The bet is re-priced to the current odds unless they have
+/// dropped more than `max_slippage_bps` below the accepted odds. Selections
+/// the backend has not priced cannot be bet on.
+pub fn price_bet(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+    accepted_odds: u32,
+    max_slippage_bps: u32,
+) -> Result<u32, ContractError> {
+    let current = get_current(env, match_id, bet_type).ok_or(ContractError::OddsStale)?;
+    reprice(&current, accepted_odds, max_slippage_bps)
+}
+
+/// Price an in-play bet as `price_bet` does. In-play prices move quickly,
+/// so the current publication must also be within the staleness window.
+pub fn price_live_bet(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+    accepted_odds: u32,
+    max_slippage_bps: u32,
+) -> Result<u32, ContractError> {
+    let current = get_current(env, match_id, bet_type).ok_or(ContractError::OddsStale)?;
+    ensure_fresh(env, &current)?;
+    reprice(&current, accepted_odds, max_slippage_bps)
+}
+
+fn reprice(
+    current: &OddsPublication,
+    accepted_odds: u32,
+    max_slippage_bps: u32,
+) -> Result<u32, ContractError> {
+    if current.odds < accepted_odds {
+        let drop = (accepted_odds - current.odds) as u64 * BPS_SCALE;
+        if drop > accepted_odds as u64 * max_slippage_bps as u64 {
+            return Err(ContractError::OddsMoved);
+        }
+    }
+    Ok(current.odds)
+}
+
+/// Decimal form of a publication version, for event metadata
+pub fn version_string(env: &Env, version: u64) -> String {
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    let mut rest = version;
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    String::from_bytes(env, &digits[start..])
+}
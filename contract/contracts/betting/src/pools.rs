@@ -0,0 +1,270 @@
+use common::{ContractError, ODDS_SCALE};
+use soroban_sdk::{contracttype, token, Address, BytesN, Env, Symbol, Vec};
+
+use crate::claims;
+use crate::matches::{self, MatchStatus};
+use crate::responsible;
+use crate::DataKey;
+
+pub const MIN_POOL_OUTCOMES: u32 = 2;
+pub const MAX_POOL_OUTCOMES: u32 = 16;
+
+const BPS_SCALE: i128 = 10_000;
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PoolStatus {
+    Open,
+    Settled,
+    Void,
+}
+
+/// Pari-mutuel market on a match. Stakes on every outcome go into one pot;
+/// once settled, backers of the winning outcome split the pot net of the
+/// house take in proportion to their stakes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pool {
+    pub match_id: BytesN<32>,
+    pub token: Address,
+    pub outcomes: Vec<Symbol>,
+    pub house_take_bps: u32,
+    pub total_staked: i128,
+    pub status: PoolStatus,
+    pub winning_outcome: Option<Symbol>,
+    pub winning_total: i128,
+    pub net_pot: i128,
+    pub claimed_stake: i128,
+    pub paid_out: i128,
+}
+
+pub fn get_pool(env: &Env, match_id: &BytesN<32>) -> Result<Pool, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Pool(match_id.clone()))
+        .ok_or(ContractError::PoolNotFound)
+}
+
+pub fn store_pool(env: &Env, pool: &Pool) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Pool(pool.match_id.clone()), pool);
+}
+
+pub fn outcome_total(env: &Env, match_id: &BytesN<32>, outcome: &Symbol) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PoolOutcomeTotal(
+            match_id.clone(),
+            outcome.clone(),
+        ))
+        .unwrap_or(0)
+}
+
+pub fn stake_of(env: &Env, match_id: &BytesN<32>, outcome: &Symbol, bettor: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PoolStake(
+            match_id.clone(),
+            outcome.clone(),
+            bettor.clone(),
+        ))
+        .unwrap_or(0)
+}
+
+pub fn validate_outcomes(outcomes: &Vec<Symbol>) -> Result<(), ContractError> {
+    if outcomes.len() < MIN_POOL_OUTCOMES || outcomes.len() > MAX_POOL_OUTCOMES {
+        return Err(ContractError::InvalidBet);
+    }
+    for (index, outcome) in outcomes.iter().enumerate() {
+        if outcomes.first_index_of(&outcome) != Some(index as u32) {
+            return Err(ContractError::InvalidBet);
+        }
+    }
+    Ok(())
+}
+
+/// Add a stake to an outcome of an open pool.
+pub fn add_stake(
+    env: &Env,
+    pool: &mut Pool,
+    outcome: &Symbol,
+    bettor: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if !pool.outcomes.contains(outcome) {
+        return Err(ContractError::InvalidBet);
+    }
+
+    let storage = env.storage().persistent();
+    let total = outcome_total(env, &pool.match_id, outcome)
+        .checked_add(amount)
+        .ok_or(ContractError::InvalidAmount)?;
+    let stake = stake_of(env, &pool.match_id, outcome, bettor)
+        .checked_add(amount)
+        .ok_or(ContractError::InvalidAmount)?;
+    pool.total_staked = pool
+        .total_staked
+        .checked_add(amount)
+        .ok_or(ContractError::InvalidAmount)?;
+
+    storage.set(
+        &DataKey::PoolOutcomeTotal(pool.match_id.clone(), outcome.clone()),
+        &total,
+    );
+    storage.set(
+        &DataKey::PoolStake(pool.match_id.clone(), outcome.clone(), bettor.clone()),
+        &stake,
+    );
+    store_pool(env, pool);
+    Ok(())
+}
+
+/// Pot left for winners after the house take.
+pub fn net_pot(pool: &Pool) -> i128 {
+    pool.total_staked - house_take(pool)
+}
+
+fn house_take(pool: &Pool) -> i128 {
+    pool.total_staked * pool.house_take_bps as i128 / BPS_SCALE
+}
+
+/// Current return per unit staked on an outcome, scaled by `ODDS_SCALE`.
+/// Zero while nothing is staked on the outcome.
+pub fn implied_odds(env: &Env, pool: &Pool, outcome: &Symbol) -> i128 {
+    let backing = outcome_total(env, &pool.match_id, outcome);
+    if backing == 0 {
+        return 0;
+    }
+    net_pot(pool) * ODDS_SCALE / backing
+}
+
+/// Close the pool on its winning outcome and send the house take to the
+/// treasury. A pool with no stake on the winning outcome is voided so every
+/// stake can be refunded.
+pub fn settle(env: &Env, pool: &mut Pool, winning_outcome: &Symbol) -> Result<(), ContractError> {
+    if pool.status != PoolStatus::Open {
+        return Err(ContractError::BetAlreadySettled);
+    }
+    if !pool.outcomes.contains(winning_outcome) {
+        return Err(ContractError::InvalidBet);
+    }
+    let record = matches::get_match(env, &pool.match_id)?;
+    if record.status != MatchStatus::Locked && record.status != MatchStatus::Settled {
+        return Err(ContractError::ResultNotFinal);
+    }
+
+    let winning_total = outcome_total(env, &pool.match_id, winning_outcome);
+    pool.winning_outcome = Some(winning_outcome.clone());
+    pool.winning_total = winning_total;
+
+    if winning_total == 0 {
+        pool.status = PoolStatus::Void;
+        pool.net_pot = pool.total_staked;
+    } else {
+        let take = house_take(pool);
+        if take > 0 {
+            let treasury = claims::get_treasury(env).ok_or(ContractError::NotInitialized)?;
+            token::Client::new(env, &pool.token).transfer(
+                &env.current_contract_address(),
+                &treasury,
+                &take,
+            );
+        }
+        pool.status = PoolStatus::Settled;
+        pool.net_pot = pool.total_staked - take;
+    }
+    store_pool(env, pool);
+    Ok(())
+}
+
+/// Count a bettor's stakes on the losing outcomes of a settled pool toward
+/// their loss limit. Each losing stake is only counted once. Returns the
+/// amount recorded.
+pub fn record_loss(env: &Env, pool: &Pool, bettor: &Address) -> Result<i128, ContractError> {
+    let lost = take_losing_stakes(env, pool, bettor)?;
+    if lost == 0 {
+        return Err(ContractError::NotClaimable);
+    }
+    responsible::record_result(env, bettor, lost, 0);
+    Ok(lost)
+}
+
+fn take_losing_stakes(env: &Env, pool: &Pool, bettor: &Address) -> Result<i128, ContractError> {
+    let winning_outcome = match (&pool.status, &pool.winning_outcome) {
+        (PoolStatus::Settled, Some(outcome)) => outcome,
+        _ => return Err(ContractError::ResultNotFinal),
+    };
+    let storage = env.storage().persistent();
+    let mut lost = 0i128;
+    for outcome in pool.outcomes.iter() {
+        if outcome == *winning_outcome {
+            continue;
+        }
+        let key = DataKey::PoolStake(pool.match_id.clone(), outcome, bettor.clone());
+        lost += storage.get::<_, i128>(&key).unwrap_or(0);
+        storage.remove(&key);
+    }
+    Ok(lost)
+}
+
+/// Pay a bettor's share of a settled pool, or refund their stakes on a
+/// void pool. Once the last winning stake is claimed, the rounding dust
+/// left in the pot goes to the treasury.
+pub fn claim(env: &Env, pool: &mut Pool, bettor: &Address) -> Result<i128, ContractError> {
+    if pool.status == PoolStatus::Open {
+        let record = matches::get_match(env, &pool.match_id)?;
+        if record.status != MatchStatus::Void {
+            return Err(ContractError::ResultNotFinal);
+        }
+        pool.status = PoolStatus::Void;
+        pool.net_pot = pool.total_staked;
+    }
+
+    let storage = env.storage().persistent();
+    let mut stake = 0i128;
+    let claimable = match (&pool.status, &pool.winning_outcome) {
+        (PoolStatus::Settled, Some(outcome)) => Vec::from_array(env, [outcome.clone()]),
+        _ => pool.outcomes.clone(),
+    };
+    for outcome in claimable.iter() {
+        let key = DataKey::PoolStake(pool.match_id.clone(), outcome, bettor.clone());
+        stake += storage.get::<_, i128>(&key).unwrap_or(0);
+        storage.remove(&key);
+    }
+    if stake == 0 {
+        return Err(ContractError::NotClaimable);
+    }
+
+    let amount = if pool.status == PoolStatus::Settled {
+        stake
+            .checked_mul(pool.net_pot)
+            .ok_or(ContractError::InvalidAmount)?
+            / pool.winning_total
+    } else {
+        stake
+    };
+    pool.claimed_stake += stake;
+    pool.paid_out += amount;
+    // Losing stakes the bettor also held are settled along with the claim
+    let lost = if pool.status == PoolStatus::Settled {
+        take_losing_stakes(env, pool, bettor)?
+    } else {
+        0
+    };
+    responsible::record_result(env, bettor, stake + lost, amount);
+
+    let token_client = token::Client::new(env, &pool.token);
+    token_client.transfer(&env.current_contract_address(), bettor, &amount);
+
+    if pool.status == PoolStatus::Settled && pool.claimed_stake == pool.winning_total {
+        let dust = pool.net_pot - pool.paid_out;
+        if dust > 0 {
+            let treasury = claims::get_treasury(env).ok_or(ContractError::NotInitialized)?;
+            token_client.transfer(&env.current_contract_address(), &treasury, &dust);
+            pool.paid_out += dust;
+        }
+    }
+    store_pool(env, pool);
+    Ok(amount)
+}
@@ -0,0 +1,114 @@
+use common::ContractError;
+use soroban_sdk::{contracttype, token, BytesN, Env, Symbol};
+
+use crate::bets::{self, BetState};
+use crate::claims;
+use crate::DataKey;
+
+/// Upper bound on bets refunded per call, keeping each batch well inside
+/// the per-transaction instruction budget.
+pub const MAX_REFUND_BATCH: u32 = 50;
+
+/// Progress of refunding the bets of a voided match.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoidProgress {
+    pub match_id: BytesN<32>,
+    pub total_bets: u32,
+    pub next_index: u32,
+    pub refunded_bets: u32,
+    pub refunded_amount: i128,
+    pub completed: bool,
+}
+
+pub fn get_progress(env: &Env, match_id: &BytesN<32>) -> Option<VoidProgress> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VoidProgress(match_id.clone()))
+}
+
+pub fn start(env: &Env, match_id: &BytesN<32>) -> VoidProgress {
+    let total_bets = bets::match_bet_count(env, match_id);
+    let progress = VoidProgress {
+        match_id: match_id.clone(),
+        total_bets,
+        next_index: 0,
+        refunded_bets: 0,
+        refunded_amount: 0,
+        completed: total_bets == 0,
+    };
+    store_progress(env, &progress);
+    progress
+}
+
+/// Refund up to `batch_size` bets of a voided match, resuming from the
+/// stored cursor. Bets that are no longer active, or that the settlement
+/// contract is handling, are skipped; a skipped bet can still be claimed
+/// once its settlement is rejected.
+pub fn process_batch(
+    env: &Env,
+    match_id: &BytesN<32>,
+    batch_size: u32,
+) -> Result<VoidProgress, ContractError> {
+    let mut progress = get_progress(env, match_id).ok_or(ContractError::InvalidStatus)?;
+    if progress.completed {
+        return Ok(progress);
+    }
+
+    let storage = env.storage().persistent();
+    let limit = batch_size.clamp(1, MAX_REFUND_BATCH);
+    let end = progress
+        .total_bets
+        .min(progress.next_index.saturating_add(limit));
+    let mut batch_amount = 0i128;
+
+    for index in progress.next_index..end {
+        let bet_id = match storage.get(&DataKey::MatchBet(match_id.clone(), index)) {
+            Some(bet_id) => bet_id,
+            None => continue,
+        };
+        let mut bet = bets::get_bet(env, &bet_id)?;
+        if bet.state != BetState::Active || claims::in_settlement(env, &bet) {
+            continue;
+        }
+
+        bet.state = BetState::Void;
+        bets::store_bet(env, &bet);
+        bets::deactivate_bet(env, &bet);
+
+        // Free bets are voided without a refund
+        let refund = bet.funded_stake();
+        if refund > 0 {
+            let token_client = token::Client::new(env, &bet.token);
+            token_client.transfer(&env.current_contract_address(), &bet.bettor, &refund);
+        }
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(env, "bet_refunded"), bet.bet_id.clone()),
+            (bet.bettor.clone(), refund),
+        );
+
+        progress.refunded_bets += 1;
+        batch_amount += refund;
+    }
+
+    progress.next_index = end;
+    progress.refunded_amount += batch_amount;
+    progress.completed = end >= progress.total_bets;
+    store_progress(env, &progress);
+
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events().publish(
+        (Symbol::new(env, "void_refunds"), match_id.clone()),
+        (progress.next_index, batch_amount, progress.completed),
+    );
+
+    Ok(progress)
+}
+
+fn store_progress(env: &Env, progress: &VoidProgress) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::VoidProgress(progress.match_id.clone()), progress);
+}
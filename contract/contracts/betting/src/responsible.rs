@@ -0,0 +1,227 @@
+use common::ContractError;
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::DataKey;
+
+pub const DAY_SECONDS: u64 = 86_400;
+pub const WEEK_SECONDS: u64 = 7 * DAY_SECONDS;
+pub const MONTH_SECONDS: u64 = 30 * DAY_SECONDS;
+
+/// Delay before a loosened limit takes effect
+pub const COOLING_OFF_SECONDS: u64 = DAY_SECONDS;
+
+/// Self-imposed caps on staking, in stake units. Zero means no cap. The
+/// loss cap bounds net losses over the current 30-day window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GamingLimits {
+    pub daily_wager: i128,
+    pub weekly_wager: i128,
+    pub monthly_wager: i128,
+    pub monthly_loss: i128,
+}
+
+/// Limits requested by the user that loosen the current ones, applied once
+/// the cooling-off delay has passed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingLimits {
+    pub limits: GamingLimits,
+    pub effective_at: u64,
+}
+
+/// Staked and lost amounts in the current day, week and 30-day windows.
+/// Window indexes are ledger timestamps divided by the window length.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WagerTotals {
+    pub day: u64,
+    pub daily: i128,
+    pub week: u64,
+    pub weekly: i128,
+    pub month: u64,
+    pub monthly: i128,
+    pub monthly_loss: i128,
+}
+
+fn no_limits() -> GamingLimits {
+    GamingLimits {
+        daily_wager: 0,
+        weekly_wager: 0,
+        monthly_wager: 0,
+        monthly_loss: 0,
+    }
+}
+
+pub fn get_pending(env: &Env, user: &Address) -> Option<PendingLimits> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingGamingLimits(user.clone()))
+}
+
+/// Limits in force for a user, applying pending limits whose cooling-off
+/// delay has passed.
+pub fn get_limits(env: &Env, user: &Address) -> GamingLimits {
+    if let Some(pending) = get_pending(env, user) {
+        if env.ledger().timestamp() >= pending.effective_at {
+            return pending.limits;
+        }
+    }
+    env.storage()
+        .persistent()
+        .get(&DataKey::GamingLimits(user.clone()))
+        .unwrap_or_else(no_limits)
+}
+
+/// Apply requested limits. Tighter values apply at once; if any value is
+/// looser, the full request is queued behind the cooling-off delay.
+pub fn set_limits(
+    env: &Env,
+    user: &Address,
+    requested: &GamingLimits,
+) -> Result<Option<PendingLimits>, ContractError> {
+    if requested.daily_wager < 0
+        || requested.weekly_wager < 0
+        || requested.monthly_wager < 0
+        || requested.monthly_loss < 0
+    {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let current = get_limits(env, user);
+    let tightened = GamingLimits {
+        daily_wager: tighter(current.daily_wager, requested.daily_wager),
+        weekly_wager: tighter(current.weekly_wager, requested.weekly_wager),
+        monthly_wager: tighter(current.monthly_wager, requested.monthly_wager),
+        monthly_loss: tighter(current.monthly_loss, requested.monthly_loss),
+    };
+
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::GamingLimits(user.clone()), &tightened);
+
+    let pending_key = DataKey::PendingGamingLimits(user.clone());
+    if tightened == *requested {
+        storage.remove(&pending_key);
+        return Ok(None);
+    }
+    let pending = PendingLimits {
+        limits: requested.clone(),
+        effective_at: env.ledger().timestamp() + COOLING_OFF_SECONDS,
+    };
+    storage.set(&pending_key, &pending);
+    Ok(Some(pending))
+}
+
+fn tighter(current: i128, requested: i128) -> i128 {
+    match (current, requested) {
+        (0, requested) => requested,
+        (current, 0) => current,
+        (current, requested) => current.min(requested),
+    }
+}
+
+pub fn excluded_until(env: &Env, user: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SelfExclusion(user.clone()))
+        .unwrap_or(0)
+}
+
+/// Exclude a user until `until`. An exclusion can be extended but never
+/// shortened.
+pub fn exclude(env: &Env, user: &Address, until: u64) -> u64 {
+    let until = until.max(excluded_until(env, user));
+    env.storage()
+        .persistent()
+        .set(&DataKey::SelfExclusion(user.clone()), &until);
+    until
+}
+
+pub fn ensure_not_excluded(env: &Env, user: &Address) -> Result<(), ContractError> {
+    if env.ledger().timestamp() < excluded_until(env, user) {
+        return Err(ContractError::SelfExcluded);
+    }
+    Ok(())
+}
+
+pub fn get_totals(env: &Env, user: &Address) -> WagerTotals {
+    let now = env.ledger().timestamp();
+    let mut totals: WagerTotals = env
+        .storage()
+        .persistent()
+        .get(&DataKey::WagerTotals(user.clone()))
+        .unwrap_or(WagerTotals {
+            day: 0,
+            daily: 0,
+            week: 0,
+            weekly: 0,
+            month: 0,
+            monthly: 0,
+            monthly_loss: 0,
+        });
+
+    if totals.day != now / DAY_SECONDS {
+        totals.day = now / DAY_SECONDS;
+        totals.daily = 0;
+    }
+    if totals.week != now / WEEK_SECONDS {
+        totals.week = now / WEEK_SECONDS;
+        totals.weekly = 0;
+    }
+    if totals.month != now / MONTH_SECONDS {
+        totals.month = now / MONTH_SECONDS;
+        totals.monthly = 0;
+        totals.monthly_loss = 0;
+    }
+    totals
+}
+
+fn store_totals(env: &Env, user: &Address, totals: &WagerTotals) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WagerTotals(user.clone()), totals);
+}
+
+/// Check a new stake against the user's exclusion and limits and count it
+/// towards their wager totals. `at_risk`, the part of the stake the user
+/// paid for, counts as a potential loss against the loss cap; it is zero
+/// for a free bet.
+pub fn record_wager(
+    env: &Env,
+    user: &Address,
+    amount: i128,
+    at_risk: i128,
+) -> Result<(), ContractError> {
+    ensure_not_excluded(env, user)?;
+
+    let limits = get_limits(env, user);
+    let mut totals = get_totals(env, user);
+    totals.daily += amount;
+    totals.weekly += amount;
+    totals.monthly += amount;
+
+    if exceeds(totals.daily, limits.daily_wager)
+        || exceeds(totals.weekly, limits.weekly_wager)
+        || exceeds(totals.monthly, limits.monthly_wager)
+    {
+        return Err(ContractError::WagerLimitExceeded);
+    }
+    if exceeds(totals.monthly_loss + at_risk, limits.monthly_loss) {
+        return Err(ContractError::LossLimitExceeded);
+    }
+
+    store_totals(env, user, &totals);
+    Ok(())
+}
+
+/// Record the outcome of a stake: `returned` is what the user got back
+/// (zero for a lost bet), so winnings reduce the net loss.
+pub fn record_result(env: &Env, user: &Address, stake: i128, returned: i128) {
+    let mut totals = get_totals(env, user);
+    totals.monthly_loss += stake - returned;
+    store_totals(env, user, &totals);
+}
+
+fn exceeds(total: i128, limit: i128) -> bool {
+    limit > 0 && total > limit
+}
@@ -0,0 +1,63 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map};
+
+use crate::DataKey;
+
+/// Ledgers after which an untouched spin record has its TTL extended
+pub const SPIN_TTL_THRESHOLD: u32 = 17_280; // ~1 day at 5s per ledger
+
+/// TTL given to a spin record when it is written, in ledgers. Once it runs
+/// out the record is archived rather than deleted, so it can be restored
+/// if a dispute needs it.
+pub const SPIN_TTL_EXTEND_TO: u32 = 518_400; // ~30 days at 5s per ledger
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpinExecution {
+    pub spin_id: BytesN<32>,
+    pub executor: Address,
+    pub timestamp: u64,
+}
+
+pub fn get_execution(env: &Env, spin_id: &BytesN<32>) -> Option<SpinExecution> {
+    let storage = env.storage().persistent();
+    if let Some(execution) = storage.get(&DataKey::SpinExecution(spin_id.clone())) {
+        return Some(execution);
+    }
+    // Executions recorded before the per-spin keys stay visible until migrated
+    storage
+        .get::<_, Map<BytesN<32>, SpinExecution>>(&DataKey::SpinExecutions)
+        .and_then(|legacy| legacy.get(spin_id.clone()))
+}
+
+pub fn store_execution(env: &Env, execution: &SpinExecution) {
+    let key = DataKey::SpinExecution(execution.spin_id.clone());
+    let storage = env.storage().persistent();
+    storage.set(&key, execution);
+    storage.extend_ttl(&key, SPIN_TTL_THRESHOLD, SPIN_TTL_EXTEND_TO);
+}
+
+/// Move up to `batch_size` executions from the legacy map to their own keys.
+/// The map is removed once empty. Returns the number of entries left.
+pub fn migrate_legacy(env: &Env, batch_size: u32) -> u32 {
+    let storage = env.storage().persistent();
+    let mut legacy: Map<BytesN<32>, SpinExecution> = match storage.get(&DataKey::SpinExecutions) {
+        Some(legacy) => legacy,
+        None => return 0,
+    };
+
+    for spin_id in legacy.keys().iter().take(batch_size as usize) {
+        if let Some(execution) = legacy.get(spin_id.clone()) {
+            if !storage.has(&DataKey::SpinExecution(spin_id.clone())) {
+                store_execution(env, &execution);
+            }
+        }
+        legacy.remove(spin_id);
+    }
+
+    if legacy.is_empty() {
+        storage.remove(&DataKey::SpinExecutions);
+    } else {
+        storage.set(&DataKey::SpinExecutions, &legacy);
+    }
+    legacy.len()
+}
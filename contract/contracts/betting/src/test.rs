@@ -1,18 +1,127 @@
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, Address, BytesN, Env, Symbol,
+    testutils::{
+        storage::Persistent as _, Address as _, Events as _, Ledger, MockAuth, MockAuthInvoke,
+    },
+    token, Address, BytesN, Env, IntoVal, InvokeError, Symbol, TryFromVal, U256,
 };
 
+const SPIN_EXPIRY: u64 = 1_000_000;
+
+fn backend_key() -> SigningKey {
+    SigningKey::from_bytes(&[42u8; 32])
+}
+
+fn register_backend_key(env: &Env, client: &BettingContractClient<'_>, backend_signer: &Address) {
+    let public_key = BytesN::from_array(env, &backend_key().verifying_key().to_bytes());
+    client.rotate_backend_signer(backend_signer, &public_key);
+}
+
+fn sign_spin_with(
+    key: &SigningKey,
+    client: &BettingContractClient<'_>,
+    spin_id: &BytesN<32>,
+    spin_hash: &BytesN<32>,
+    executor: &Address,
+    expires_at: u64,
+) -> BytesN<64> {
+    let payload = client.spin_signing_payload(spin_id, spin_hash, executor, &expires_at);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    BytesN::from_array(&client.env, &key.sign(&message).to_bytes())
+}
+
+fn sign_spin(
+    client: &BettingContractClient<'_>,
+    spin_id: &BytesN<32>,
+    spin_hash: &BytesN<32>,
+    executor: &Address,
+) -> BytesN<64> {
+    sign_spin_with(
+        &backend_key(),
+        client,
+        spin_id,
+        spin_hash,
+        executor,
+        SPIN_EXPIRY,
+    )
+}
+
+/// Same as `setup_test`, but leaves no auths mocked once the contract is configured
+fn setup_test_with_auths(env: &Env) -> (BettingContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let setup = setup_test(env);
+    env.set_auths(&[]);
+    setup
+}
+
+fn create_open_match(
+    env: &Env,
+    client: &BettingContractClient<'_>,
+    admin: &Address,
+    match_id: &BytesN<32>,
+) {
+    let now = env.ledger().timestamp();
+    client.create_match(admin, match_id, &now, &(now + 3_600));
+    client.update_match_status(admin, match_id, &MatchStatus::Open);
+}
+
+fn publish_odds(
+    client: &BettingContractClient<'_>,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+    odds: u32,
+    version: u64,
+) -> OddsPublication {
+    let publication = OddsPublication {
+        match_id: match_id.clone(),
+        bet_type: bet_type.clone(),
+        odds,
+        version,
+        published_at: client.env.ledger().timestamp(),
+    };
+    let payload = client.odds_signing_payload(&publication);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = BytesN::from_array(&client.env, &backend_key().sign(&message).to_bytes());
+    client.publish_odds(&publication, &signature);
+    publication
+}
+
+/// Publish `odds` as the house's current price of a selection
+fn offer_odds(
+    client: &BettingContractClient<'_>,
+    match_id: &BytesN<32>,
+    bet_type: &Symbol,
+    odds: u32,
+) {
+    let version = client
+        .get_live_odds(match_id, bet_type)
+        .map_or(1, |current| current.version + 1);
+    publish_odds(client, match_id, bet_type, odds, version);
+}
+
+/// Create a Stellar asset and accept it for betting.
+fn register_token(env: &Env, client: &BettingContractClient<'_>, admin: &Address) -> Address {
+    let token_admin = Address::generate(env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    client.set_token_config(admin, &token_id, &1, &1_000_000, &7);
+    token_id
+}
+
 fn setup_test(env: &Env) -> (BettingContractClient<'_>, Address, Address) {
-    let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(env, &contract_id);
-    let backend_signer = Address::generate(env);
+    // One account holds both roles; role separation has its own tests
+    let admin = Address::generate(env);
     let bettor = Address::generate(env);
-    client.initialize(&backend_signer);
-    (client, backend_signer, bettor)
+    let contract_id = env.register(BettingContract, (admin.clone(), admin.clone()));
+    let client = BettingContractClient::new(env, &contract_id);
+    register_backend_key(env, &client, &admin);
+    (client, admin, bettor)
 }
 
 #[test]
@@ -20,10 +129,8 @@ fn test_place_bet_success() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _backend_signer, bettor) = setup_test(&env);
-    let token_admin = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract_v2(token_admin.clone());
-    let token_id = token_contract.address();
+    let (client, backend_signer, bettor) = setup_test(&env);
+    let token_id = register_token(&env, &client, &backend_signer);
     let token_client = token::Client::new(&env, &token_id);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
 
@@ -31,9 +138,11 @@ fn test_place_bet_success() {
     token_admin_client.mint(&bettor, &amount);
 
     let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &backend_signer, &match_id);
     let bet_type = Symbol::new(&env, "win");
     let odds = 200; // 2.00
 
+    offer_odds(&client, &match_id, &bet_type, odds);
     let result = client.try_place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
     assert!(result.is_ok());
 
@@ -42,21 +151,91 @@ fn test_place_bet_success() {
     assert_eq!(token_client.balance(&client.address), amount);
 }
 
+#[test]
+fn test_place_bet_emits_typed_bet_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let bet_type = Symbol::new(&env, "home");
+
+    offer_odds(&client, &match_id, &bet_type, 180);
+    let bet_id = client.place_bet(&bettor, &token_id, &250, &match_id, &bet_type, &180);
+
+    let bet_events: std::vec::Vec<common::BetEvent> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == client.address
+                && Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(common::BET_EVENT)
+        })
+        .map(|(_, _, data)| data.into_val(&env))
+        .collect();
+    assert_eq!(bet_events.len(), 1);
+    let event = &bet_events[0];
+    assert_eq!(event.bet_id, bet_id);
+    assert_eq!(event.bettor, bettor);
+    assert_eq!(event.amount, 250);
+    assert_eq!(event.token, token_id);
+    assert_eq!(event.bet_type, bet_type);
+    assert_eq!(event.odds, 180);
+    assert_eq!(
+        event.metadata.get(Symbol::new(&env, "odds_version")),
+        Some(soroban_sdk::String::from_str(&env, "1"))
+    );
+}
+
+#[test]
+fn test_legacy_bet_event_can_be_switched_off() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let bet_type = Symbol::new(&env, "home");
+    let legacy_topic = Symbol::new(&env, "bet_placed");
+    let legacy_events = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(legacy_topic.clone())
+            })
+            .count()
+    };
+
+    assert!(client.is_legacy_bet_events_enabled());
+    offer_odds(&client, &match_id, &bet_type, 200);
+    client.place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &200);
+    assert_eq!(legacy_events(&env), 1);
+
+    client.set_legacy_bet_events(&admin, &false);
+    assert!(!client.is_legacy_bet_events_enabled());
+    offer_odds(&client, &match_id, &bet_type, 200);
+    client.place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &200);
+    assert_eq!(legacy_events(&env), 0);
+}
+
 #[test]
 fn test_prevent_double_betting() {
     let env = Env::default();
     env.mock_all_auths();
 
     let (client, backend_signer, bettor) = setup_test(&env);
-    let token_admin = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract_v2(token_admin);
-    let token_id = token_contract.address();
+    let token_id = register_token(&env, &client, &backend_signer);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
 
     let amount = 1000i128;
     token_admin_client.mint(&bettor, &(amount * 2));
 
     let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &backend_signer, &match_id);
     let bet_type = Symbol::new(&env, "win");
     let odds = 200;
 
@@ -65,9 +244,11 @@ fn test_prevent_double_betting() {
     assert!(client.is_double_betting_prevented());
 
     // First bet
+    offer_odds(&client, &match_id, &bet_type, odds);
     client.place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
 
     // Second bet on same match by same user should fail
+    offer_odds(&client, &match_id, &bet_type, odds);
     let result = client.try_place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
     assert!(result.is_err());
 }
@@ -78,27 +259,2130 @@ fn test_allow_double_betting_when_disabled() {
     env.mock_all_auths();
 
     let (client, backend_signer, bettor) = setup_test(&env);
-    let token_admin = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract_v2(token_admin);
-    let token_id = token_contract.address();
+    let token_id = register_token(&env, &client, &backend_signer);
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let amount = 1000i128;
+    token_admin_client.mint(&bettor, &(amount * 2));
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &backend_signer, &match_id);
+    let bet_type = Symbol::new(&env, "win");
+    let odds = 200;
+
+    // Ensure double betting is allowed (default)
+    assert!(!client.is_double_betting_prevented());
+
+    // First bet
+    offer_odds(&client, &match_id, &bet_type, odds);
+    client.place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
+
+    // Second bet on same match by same user should succeed
+    offer_odds(&client, &match_id, &bet_type, odds);
+    let result = client.try_place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_multiple_bets_same_match_get_unique_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, backend_signer, bettor) = setup_test(&env);
+    let token_id = register_token(&env, &client, &backend_signer);
+    let token_client = token::Client::new(&env, &token_id);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
 
-    let amount = 1000i128;
-    token_admin_client.mint(&bettor, &(amount * 2));
+    token_admin_client.mint(&bettor, &1500);
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &backend_signer, &match_id);
+    let odds = 200;
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), odds);
+    let first = client.place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &odds,
+    );
+    offer_odds(&client, &match_id, &Symbol::new(&env, "over_2_5"), odds);
+    let second = client.place_bet(
+        &bettor,
+        &token_id,
+        &500,
+        &match_id,
+        &Symbol::new(&env, "over_2_5"),
+        &odds,
+    );
+    assert_eq!(first, U256::from_u32(&env, 1));
+    assert_eq!(second, U256::from_u32(&env, 2));
+
+    // Both stakes remain tracked
+    assert_eq!(client.get_bet(&first).amount, 1000);
+    assert_eq!(client.get_bet(&second).amount, 500);
+    assert_eq!(token_client.balance(&client.address), 1500);
+
+    // Both bets are indexed per user and per match
+    let user_bets = client.get_user_bets(&bettor);
+    assert_eq!(user_bets.len(), 2);
+    assert_eq!(user_bets.get(0).unwrap(), first);
+    assert_eq!(user_bets.get(1).unwrap(), second);
+
+    assert_eq!(client.get_match_bet_count(&match_id), 2);
+    let page = client.get_match_bets(&match_id, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap(), second);
+}
+
+fn bet_request(env: &Env, match_id: &BytesN<32>, bet_type: &str, amount: i128) -> BetRequest {
+    BetRequest {
+        amount,
+        match_id: match_id.clone(),
+        bet_type: Symbol::new(env, bet_type),
+        odds: 200,
+    }
+}
+
+#[test]
+fn test_place_bets_escrows_total_stake_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    let first_match = BytesN::from_array(&env, &[1u8; 32]);
+    let second_match = BytesN::from_array(&env, &[2u8; 32]);
+    create_open_match(&env, &client, &admin, &first_match);
+    create_open_match(&env, &client, &admin, &second_match);
+
+    offer_odds(&client, &first_match, &Symbol::new(&env, "home"), 200);
+    offer_odds(&client, &first_match, &Symbol::new(&env, "over_2_5"), 200);
+    offer_odds(&client, &second_match, &Symbol::new(&env, "away"), 200);
+    let requests = soroban_sdk::vec![
+        &env,
+        bet_request(&env, &first_match, "home", 300),
+        bet_request(&env, &first_match, "over_2_5", 200),
+        bet_request(&env, &second_match, "away", 100),
+    ];
+    let bet_ids = client.place_bets(&bettor, &token_id, &requests);
+
+    assert_eq!(
+        bet_ids,
+        soroban_sdk::vec![
+            &env,
+            U256::from_u32(&env, 1),
+            U256::from_u32(&env, 2),
+            U256::from_u32(&env, 3)
+        ]
+    );
+    assert_eq!(client.get_bet(&bet_ids.get(2).unwrap()).amount, 100);
+    assert_eq!(client.get_user_bets(&bettor), bet_ids);
+    assert_eq!(token_client.balance(&bettor), 400);
+    assert_eq!(token_client.balance(&client.address), 600);
+}
+
+#[test]
+fn test_place_bets_rejects_whole_batch_on_any_invalid_bet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    let open_match = BytesN::from_array(&env, &[1u8; 32]);
+    let unknown_match = BytesN::from_array(&env, &[9u8; 32]);
+    create_open_match(&env, &client, &admin, &open_match);
+
+    offer_odds(&client, &open_match, &Symbol::new(&env, "home"), 200);
+    offer_odds(&client, &unknown_match, &Symbol::new(&env, "home"), 200);
+    let requests = soroban_sdk::vec![
+        &env,
+        bet_request(&env, &open_match, "home", 300),
+        bet_request(&env, &unknown_match, "home", 200),
+    ];
+    assert_eq!(
+        client.try_place_bets(&bettor, &token_id, &requests),
+        Err(Ok(ContractError::MatchNotFound))
+    );
+    assert_eq!(
+        client.try_place_bets(&bettor, &token_id, &soroban_sdk::Vec::new(&env)),
+        Err(Ok(ContractError::InvalidBet))
+    );
+
+    assert!(client.get_user_bets(&bettor).is_empty());
+    assert_eq!(client.get_match_bet_count(&open_match), 0);
+    assert_eq!(token_client.balance(&bettor), 1000);
+}
+
+#[test]
+fn test_get_bet_for_nonexistent_id_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _) = setup_test(&env);
+
+    let result = client.try_get_bet(&U256::from_u32(&env, 99));
+    assert_eq!(result, Err(Ok(ContractError::BetNotFound)));
+}
+
+#[test]
+fn test_place_bet_with_unlisted_token_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    token::StellarAssetClient::new(&env, &token_id).mint(&bettor, &1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+    let result = client.try_place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &200,
+    );
+    assert_eq!(result, Err(Ok(ContractError::TokenNotAllowed)));
+}
+
+#[test]
+fn test_place_bet_enforces_token_stake_bounds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    client.set_token_config(&admin, &token_id, &100, &500, &7);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let bet_type = Symbol::new(&env, "win");
+
+    offer_odds(&client, &match_id, &bet_type, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &99, &match_id, &bet_type, &200),
+        Err(Ok(ContractError::BelowMinStake))
+    );
+    offer_odds(&client, &match_id, &bet_type, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &501, &match_id, &bet_type, &200),
+        Err(Ok(ContractError::AboveMaxStake))
+    );
+
+    offer_odds(&client, &match_id, &bet_type, 200);
+    let bet_id = client.place_bet(&bettor, &token_id, &500, &match_id, &bet_type, &200);
+    assert_eq!(client.get_bet(&bet_id).token, token_id);
+
+    // Removed tokens are no longer accepted
+    client.remove_token(&admin, &token_id);
+    assert_eq!(client.get_token_config(&token_id), None);
+    assert!(client.get_allowed_tokens().is_empty());
+    offer_odds(&client, &match_id, &bet_type, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &200),
+        Err(Ok(ContractError::TokenNotAllowed))
+    );
+}
+
+#[test]
+fn test_set_token_config_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let token_id = register_token(&env, &client, &admin);
+
+    assert_eq!(
+        client.get_token_config(&token_id),
+        Some(TokenConfig {
+            token: token_id.clone(),
+            min_stake: 1,
+            max_stake: 1_000_000,
+            decimals: 7,
+        })
+    );
+    assert_eq!(client.get_allowed_tokens().len(), 1);
+
+    assert_eq!(
+        client.try_set_token_config(&admin, &token_id, &100, &10, &7),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_token_config(&admin, &token_id, &1, &10, &18),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_token_config(&Address::generate(&env), &token_id, &1, &10, &7),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_remove_token(&admin, &Address::generate(&env)),
+        Err(Ok(ContractError::TokenNotAllowed))
+    );
+}
+
+// ============================================
+// Match Registry Tests
+// ============================================
+
+fn fund_bettor(
+    env: &Env,
+    client: &BettingContractClient<'_>,
+    admin: &Address,
+    bettor: &Address,
+    amount: i128,
+) -> Address {
+    let token_id = register_token(env, client, admin);
+    token::StellarAssetClient::new(env, &token_id).mint(bettor, &amount);
+    token_id
+}
+
+#[test]
+fn test_match_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+
+    let created = client.create_match(&admin, &match_id, &100, &200);
+    assert_eq!(created.status, MatchStatus::Scheduled);
+
+    for status in [MatchStatus::Open, MatchStatus::Locked, MatchStatus::Settled] {
+        assert_eq!(
+            client
+                .update_match_status(&admin, &match_id, &status)
+                .status,
+            status
+        );
+    }
+    assert_eq!(client.get_match(&match_id).status, MatchStatus::Settled);
+
+    // Settled matches are final
+    let result = client.try_void_match(&admin, &match_id, &10);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_match_cannot_skip_states() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    client.create_match(&admin, &match_id, &100, &200);
+
+    let result = client.try_update_match_status(&admin, &match_id, &MatchStatus::Settled);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatus)));
+
+    // Voiding must go through void_match so stakes are refunded
+    let result = client.try_update_match_status(&admin, &match_id, &MatchStatus::Void);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatus)));
+
+    client.void_match(&admin, &match_id, &10);
+    assert_eq!(client.get_match(&match_id).status, MatchStatus::Void);
+}
+
+#[test]
+fn test_create_match_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, stranger) = setup_test(&env);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        client.try_create_match(&admin, &match_id, &200, &200),
+        Err(Ok(ContractError::InvalidTimeWindow))
+    );
+    assert_eq!(
+        client.try_create_match(&stranger, &match_id, &100, &200),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.create_match(&admin, &match_id, &100, &200);
+    assert_eq!(
+        client.try_create_match(&admin, &match_id, &100, &200),
+        Err(Ok(ContractError::MatchAlreadyExists))
+    );
+}
+
+#[test]
+fn test_place_bet_on_unknown_match_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+    let result = client.try_place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &200,
+    );
+    assert_eq!(result, Err(Ok(ContractError::MatchNotFound)));
+}
+
+#[test]
+fn test_place_bet_outside_betting_window_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let bet_type = Symbol::new(&env, "win");
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.create_match(&admin, &match_id, &100, &200);
+
+    // Scheduled matches do not accept bets
+    offer_odds(&client, &match_id, &bet_type, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &200),
+        Err(Ok(ContractError::BettingClosed))
+    );
+
+    // Open but before the window starts
+    client.update_match_status(&admin, &match_id, &MatchStatus::Open);
+    assert!(!client.is_betting_open(&match_id));
+    offer_odds(&client, &match_id, &bet_type, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &200),
+        Err(Ok(ContractError::BettingClosed))
+    );
+
+    // Inside the window
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert!(client.is_betting_open(&match_id));
+    offer_odds(&client, &match_id, &bet_type, 200);
+    client.place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &200);
+
+    // After kickoff, even if the admin has not locked the match yet
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    offer_odds(&client, &match_id, &bet_type, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &200),
+        Err(Ok(ContractError::BettingClosed))
+    );
+}
+
+#[test]
+fn test_place_bet_on_locked_match_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    client.update_match_status(&admin, &match_id, &MatchStatus::Locked);
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+    let result = client.try_place_bet(
+        &bettor,
+        &token_id,
+        &100,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &200,
+    );
+    assert_eq!(result, Err(Ok(ContractError::BettingClosed)));
+}
+
+// ============================================
+// Cancellation Tests
+// ============================================
+
+#[test]
+fn test_cancel_bet_refunds_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+    let bet_id = client.place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &200,
+    );
+    assert_eq!(client.cancel_bet(&bettor, &bet_id), 1000);
+
+    assert_eq!(token_client.balance(&bettor), 1000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.get_bet(&bet_id).state, BetState::Cancelled);
+    assert!(client.get_user_bets(&bettor).is_empty());
+
+    // A cancelled bet cannot be cancelled again
+    assert_eq!(
+        client.try_cancel_bet(&bettor, &bet_id),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_cancel_bet_applies_cancellation_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    client.set_cancellation_fee(&admin, &250); // 2.5%
+    assert_eq!(client.get_cancellation_fee(), 250);
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+    let bet_id = client.place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &200,
+    );
+    assert_eq!(client.cancel_bet(&bettor, &bet_id), 975);
+
+    assert_eq!(token_client.balance(&bettor), 975);
+    assert_eq!(token_client.balance(&client.address), 25);
+    assert_eq!(client.get_collected_fees(&token_id), 25);
+
+    // Retained fees are moved out of escrow to the treasury by the admin
+    let treasury = Address::generate(&env);
+    assert_eq!(
+        client.try_withdraw_fees(&admin, &token_id),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    client.set_treasury(&admin, &treasury);
+    assert_eq!(
+        client.try_withdraw_fees(&bettor, &token_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.withdraw_fees(&admin, &token_id), 25);
+    assert_eq!(token_client.balance(&treasury), 25);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.get_collected_fees(&token_id), 0);
+    assert_eq!(client.withdraw_fees(&admin, &token_id), 0);
+}
+
+#[test]
+fn test_cancel_bet_after_lock_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+    let bet_id = client.place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &200,
+    );
+    client.update_match_status(&admin, &match_id, &MatchStatus::Locked);
+
+    assert_eq!(
+        client.try_cancel_bet(&bettor, &bet_id),
+        Err(Ok(ContractError::BettingClosed))
+    );
+}
+
+#[test]
+fn test_cancel_bet_by_other_user_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+    let bet_id = client.place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &200,
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_bet(&other, &bet_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_set_cancellation_fee_above_max_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+
+    assert_eq!(
+        client.try_set_cancellation_fee(&admin, &10_001),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
+
+// ============================================
+// Void Match Tests
+// ============================================
+
+#[test]
+fn test_void_match_refunds_bets_in_batches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let token_id = register_token(&env, &client, &admin);
+    let token_client = token::Client::new(&env, &token_id);
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    let mut bettors = std::vec::Vec::new();
+    for _ in 0..5 {
+        let bettor = Address::generate(&env);
+        token_admin_client.mint(&bettor, &100);
+        offer_odds(&client, &match_id, &Symbol::new(&env, "win"), 200);
+        client.place_bet(
+            &bettor,
+            &token_id,
+            &100,
+            &match_id,
+            &Symbol::new(&env, "win"),
+            &200,
+        );
+        bettors.push(bettor);
+    }
+
+    // A cancelled bet is skipped by the refund run
+    let cancelled = client.get_user_bets(&bettors[4]).get(0).unwrap();
+    client.cancel_bet(&bettors[4], &cancelled);
+
+    let progress = client.void_match(&admin, &match_id, &2);
+    assert_eq!(client.get_match(&match_id).status, MatchStatus::Void);
+    assert_eq!(progress.total_bets, 5);
+    assert_eq!(progress.next_index, 2);
+    assert_eq!(progress.refunded_bets, 2);
+    assert!(!progress.completed);
+    assert_eq!(token_client.balance(&client.address), 200);
+
+    let progress = client.process_void_refunds(&match_id, &2);
+    assert_eq!(progress.next_index, 4);
+    assert!(!progress.completed);
+
+    let progress = client.process_void_refunds(&match_id, &2);
+    assert_eq!(progress.next_index, 5);
+    assert_eq!(progress.refunded_bets, 4);
+    assert_eq!(progress.refunded_amount, 400);
+    assert!(progress.completed);
+    assert_eq!(client.get_void_progress(&match_id), Some(progress));
+
+    for bettor in bettors.iter() {
+        assert_eq!(token_client.balance(bettor), 100);
+        assert!(client.get_user_bets(bettor).is_empty());
+    }
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(
+        client.get_bet(&U256::from_u32(&env, 1)).state,
+        BetState::Void
+    );
+
+    // Further calls are no-ops once complete
+    let again = client.process_void_refunds(&match_id, &2);
+    assert_eq!(again.refunded_amount, 400);
+}
+
+#[test]
+fn test_void_match_requires_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let contract_id = env.register(BettingContract, (admin.clone(), operator.clone()));
+    let client = BettingContractClient::new(&env, &contract_id);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &operator, &match_id);
+
+    assert_eq!(
+        client.try_void_match(&stranger, &match_id, &10),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    // Voiding is an operational call, so the admin cannot void either
+    assert_eq!(
+        client.try_void_match(&admin, &match_id, &10),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.void_match(&operator, &match_id, &10);
+}
+
+#[test]
+fn test_process_void_refunds_on_active_match_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    assert_eq!(client.get_void_progress(&match_id), None);
+    assert_eq!(
+        client.try_process_void_refunds(&match_id, &10),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
+
+// ============================================
+// Accumulator Tests
+// ============================================
+
+#[test]
+fn test_exposure_tracks_potential_payouts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    let away = Symbol::new(&env, "away");
+
+    offer_odds(&client, &match_id, &home, 250);
+    let home_bet = client.place_bet(&bettor, &token_id, &100, &match_id, &home, &250);
+    offer_odds(&client, &match_id, &away, 150);
+    client.place_bet(&bettor, &token_id, &200, &match_id, &away, &150);
+
+    assert_eq!(client.get_selection_exposure(&match_id, &home), 250);
+    assert_eq!(client.get_selection_exposure(&match_id, &away), 300);
+    assert_eq!(client.get_match_exposure(&match_id), 550);
+
+    // Cancelling a bet releases its liability
+    client.cancel_bet(&bettor, &home_bet);
+    assert_eq!(client.get_selection_exposure(&match_id, &home), 0);
+    assert_eq!(client.get_match_exposure(&match_id), 300);
+}
+
+#[test]
+fn test_place_bet_rejects_stakes_over_liability_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    let away = Symbol::new(&env, "away");
+
+    client.set_liability_limits(&admin, &match_id, &1_000, &600);
+    assert_eq!(
+        client.get_liability_limits(&match_id),
+        LiabilityLimits {
+            max_match_liability: 1_000,
+            max_selection_liability: 600,
+        }
+    );
+
+    offer_odds(&client, &match_id, &home, 300);
+    client.place_bet(&bettor, &token_id, &200, &match_id, &home, &300);
+    offer_odds(&client, &match_id, &home, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &10, &match_id, &home, &200),
+        Err(Ok(ContractError::LiabilityExceeded))
+    );
+
+    offer_odds(&client, &match_id, &away, 200);
+    client.place_bet(&bettor, &token_id, &200, &match_id, &away, &200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &away, &200),
+        Err(Ok(ContractError::LiabilityExceeded))
+    );
+
+    // Rejected stakes are not escrowed and leave exposure unchanged
+    assert_eq!(token_client.balance(&bettor), 600);
+    assert_eq!(client.get_match_exposure(&match_id), 1_000);
+}
+
+#[test]
+fn test_set_liability_limits_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        client.try_set_liability_limits(&admin, &match_id, &100, &100),
+        Err(Ok(ContractError::MatchNotFound))
+    );
+
+    create_open_match(&env, &client, &admin, &match_id);
+    assert_eq!(
+        client.try_set_liability_limits(&admin, &match_id, &-1, &100),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_liability_limits(&Address::generate(&env), &match_id, &100, &100),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+fn settle_match(
+    client: &BettingContractClient<'_>,
+    admin: &Address,
+    match_id: &BytesN<32>,
+    results: &[(&Symbol, SelectionResult)],
+) {
+    client.update_match_status(admin, match_id, &MatchStatus::Locked);
+    for (bet_type, result) in results {
+        client.set_selection_result(admin, match_id, bet_type, result);
+    }
+    client.update_match_status(admin, match_id, &MatchStatus::Settled);
+}
+
+#[test]
+fn test_claim_pays_winnings_from_escrow_and_bankroll() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    token::StellarAssetClient::new(&env, &token_id).mint(&client.address, &5_000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    offer_odds(&client, &match_id, &home, 250);
+    let bet_id = client.place_bet(&bettor, &token_id, &1000, &match_id, &home, &250);
+
+    // Nothing can be claimed before the match is settled
+    assert_eq!(
+        client.try_claim(&bettor, &bet_id),
+        Err(Ok(ContractError::ResultNotFinal))
+    );
+
+    settle_match(&client, &admin, &match_id, &[(&home, SelectionResult::Won)]);
+
+    assert_eq!(
+        client.try_claim(&Address::generate(&env), &bet_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.claim(&bettor, &bet_id), 2_500);
+    assert_eq!(token_client.balance(&bettor), 2_500);
+    assert_eq!(token_client.balance(&client.address), 3_500);
+    assert_eq!(client.get_bet(&bet_id).state, BetState::Won);
+    assert_eq!(client.get_match_exposure(&match_id), 0);
+
+    assert_eq!(
+        client.try_claim(&bettor, &bet_id),
+        Err(Ok(ContractError::BetAlreadySettled))
+    );
+}
+
+#[test]
+fn test_claim_rejects_lost_bets_and_refunds_void_selections() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    let draw = Symbol::new(&env, "draw");
+
+    offer_odds(&client, &match_id, &home, 200);
+    let lost = client.place_bet(&bettor, &token_id, &400, &match_id, &home, &200);
+    offer_odds(&client, &match_id, &draw, 300);
+    let void = client.place_bet(&bettor, &token_id, &600, &match_id, &draw, &300);
+    settle_match(
+        &client,
+        &admin,
+        &match_id,
+        &[
+            (&home, SelectionResult::Lost),
+            (&draw, SelectionResult::Void),
+        ],
+    );
+
+    assert_eq!(
+        client.try_claim(&bettor, &lost),
+        Err(Ok(ContractError::NotClaimable))
+    );
+    assert_eq!(client.claim(&bettor, &void), 600);
+    assert_eq!(client.get_bet(&void).state, BetState::Void);
+    assert_eq!(token_client.balance(&bettor), 600);
+}
+
+#[test]
+fn test_claim_without_bankroll_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    offer_odds(&client, &match_id, &home, 200);
+    let bet_id = client.place_bet(&bettor, &token_id, &1000, &match_id, &home, &200);
+    settle_match(&client, &admin, &match_id, &[(&home, SelectionResult::Won)]);
+
+    assert_eq!(
+        client.try_claim(&bettor, &bet_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(client.get_bet(&bet_id).state, BetState::Active);
+}
+
+#[test]
+fn test_sweep_losing_stakes_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let token_id = register_token(&env, &client, &admin);
+    let token_client = token::Client::new(&env, &token_id);
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+    let treasury = Address::generate(&env);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    let away = Symbol::new(&env, "away");
+    let draw = Symbol::new(&env, "draw");
+
+    for bet_type in [&home, &away, &home, &draw, &home] {
+        let bettor = Address::generate(&env);
+        token_admin_client.mint(&bettor, &100);
+        offer_odds(&client, &match_id, bet_type, 200);
+        client.place_bet(&bettor, &token_id, &100, &match_id, bet_type, &200);
+    }
+    settle_match(
+        &client,
+        &admin,
+        &match_id,
+        &[
+            (&home, SelectionResult::Lost),
+            (&away, SelectionResult::Won),
+        ],
+    );
+
+    assert_eq!(
+        client.try_sweep_losing_stakes(&match_id, &10),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    client.set_treasury(&admin, &treasury);
+    assert_eq!(client.get_treasury(), Some(treasury.clone()));
+
+    // The unrecorded "draw" selection settles as lost instead of holding the cursor
+    assert_eq!(client.sweep_losing_stakes(&match_id, &10), 0);
+    assert_eq!(token_client.balance(&treasury), 400);
+    assert_eq!(
+        client.get_bet(&U256::from_u32(&env, 4)).state,
+        BetState::Lost
+    );
+    assert_eq!(
+        client.try_set_selection_result(&admin, &match_id, &draw, &SelectionResult::Won),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    assert_eq!(
+        client.get_bet(&U256::from_u32(&env, 1)).state,
+        BetState::Lost
+    );
+    assert_eq!(
+        client.get_bet(&U256::from_u32(&env, 2)).state,
+        BetState::Active
+    );
+}
+
+fn sign_cash_out(client: &BettingContractClient<'_>, quote: &CashOutQuote) -> BytesN<64> {
+    let payload = client.cash_out_signing_payload(quote);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    BytesN::from_array(&client.env, &backend_key().sign(&message).to_bytes())
+}
+
+fn cash_out_quote(env: &Env, bet_id: &U256, amount: i128, nonce: u8) -> CashOutQuote {
+    CashOutQuote {
+        bet_id: bet_id.clone(),
+        amount,
+        expires_at: SPIN_EXPIRY,
+        nonce: BytesN::from_array(env, &[nonce; 32]),
+    }
+}
+
+#[test]
+fn test_cash_out_closes_bet_at_quoted_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let token_client = token::Client::new(&env, &token_id);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    offer_odds(&client, &match_id, &home, 250);
+    let bet_id = client.place_bet(&bettor, &token_id, &1000, &match_id, &home, &250);
+    client.update_match_status(&admin, &match_id, &MatchStatus::Locked);
+
+    let quote = cash_out_quote(&env, &bet_id, 800, 1);
+    let signature = sign_cash_out(&client, &quote);
+    assert_eq!(client.cash_out(&bettor, &quote, &signature), 800);
+
+    assert_eq!(token_client.balance(&bettor), 800);
+    assert_eq!(token_client.balance(&client.address), 200);
+    assert_eq!(client.get_bet(&bet_id).state, BetState::CashedOut);
+    assert!(client.get_user_bets(&bettor).is_empty());
+    assert_eq!(client.get_match_exposure(&match_id), 0);
+
+    // A cashed-out bet can neither be cashed out again nor claimed
+    let again = cash_out_quote(&env, &bet_id, 800, 2);
+    let signature = sign_cash_out(&client, &again);
+    assert_eq!(
+        client.try_cash_out(&bettor, &again, &signature),
+        Err(Ok(ContractError::BetAlreadySettled))
+    );
+    client.set_selection_result(&admin, &match_id, &home, &SelectionResult::Won);
+    client.update_match_status(&admin, &match_id, &MatchStatus::Settled);
+    assert_eq!(
+        client.try_claim(&bettor, &bet_id),
+        Err(Ok(ContractError::BetAlreadySettled))
+    );
+}
+
+#[test]
+fn test_cash_out_quote_nonce_is_single_use() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    offer_odds(&client, &match_id, &home, 200);
+    let first = client.place_bet(&bettor, &token_id, &500, &match_id, &home, &200);
+    let second = client.place_bet(&bettor, &token_id, &500, &match_id, &home, &200);
+
+    let quote = cash_out_quote(&env, &first, 400, 7);
+    client.cash_out(&bettor, &quote, &sign_cash_out(&client, &quote));
+
+    let reused = cash_out_quote(&env, &second, 400, 7);
+    assert_eq!(
+        client.try_cash_out(&bettor, &reused, &sign_cash_out(&client, &reused)),
+        Err(Ok(ContractError::DuplicateOperation))
+    );
+    assert_eq!(client.get_bet(&second).state, BetState::Active);
+}
+
+#[test]
+fn test_cash_out_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    offer_odds(&client, &match_id, &home, 200);
+    let bet_id = client.place_bet(&bettor, &token_id, &1000, &match_id, &home, &200);
+
+    let quote = cash_out_quote(&env, &bet_id, 500, 1);
+    let signature = sign_cash_out(&client, &quote);
+    assert_eq!(
+        client.try_cash_out(&Address::generate(&env), &quote, &signature),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    let mut expired = cash_out_quote(&env, &bet_id, 500, 2);
+    expired.expires_at = env.ledger().timestamp();
+    let signature = sign_cash_out(&client, &expired);
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    assert_eq!(
+        client.try_cash_out(&bettor, &expired, &signature),
+        Err(Ok(ContractError::SignatureExpired))
+    );
+
+    // Quotes cannot exceed what the contract holds
+    let rich = cash_out_quote(&env, &bet_id, 5_000, 3);
+    let signature = sign_cash_out(&client, &rich);
+    assert_eq!(
+        client.try_cash_out(&bettor, &rich, &signature),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_cash_out_with_tampered_quote_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    offer_odds(&client, &match_id, &Symbol::new(&env, "home"), 200);
+    let bet_id = client.place_bet(
+        &bettor,
+        &token_id,
+        &1000,
+        &match_id,
+        &Symbol::new(&env, "home"),
+        &200,
+    );
+
+    let quote = cash_out_quote(&env, &bet_id, 500, 1);
+    let signature = sign_cash_out(&client, &quote);
+    let tampered = cash_out_quote(&env, &bet_id, 900, 1);
+    client.cash_out(&bettor, &tampered, &signature);
+}
+
+fn free_bet_voucher(
+    env: &Env,
+    bettor: &Address,
+    token_id: &Address,
+    amount: i128,
+    markets: &[&BytesN<32>],
+) -> FreeBetVoucher {
+    let mut eligible = soroban_sdk::Vec::new(env);
+    for match_id in markets {
+        eligible.push_back((*match_id).clone());
+    }
+    FreeBetVoucher {
+        voucher_id: BytesN::from_array(env, &[5u8; 32]),
+        user: bettor.clone(),
+        token: token_id.clone(),
+        amount,
+        expires_at: SPIN_EXPIRY,
+        markets: eligible,
+    }
+}
+
+fn sign_free_bet(client: &BettingContractClient<'_>, voucher: &FreeBetVoucher) -> BytesN<64> {
+    let payload = client.free_bet_signing_payload(voucher);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    BytesN::from_array(&client.env, &backend_key().sign(&message).to_bytes())
+}
+
+#[test]
+fn test_winning_free_bet_pays_winnings_without_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = register_token(&env, &client, &admin);
+    let token_client = token::Client::new(&env, &token_id);
+    token::StellarAssetClient::new(&env, &token_id).mint(&client.address, &5_000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    // The stake is neither the bettor's to lose nor the house's to pay back
+    client.set_gaming_limits(&bettor, &gaming_limits(0, 0, 0, 50));
+    client.set_liability_limits(&admin, &match_id, &0, &150);
+
+    let voucher = free_bet_voucher(&env, &bettor, &token_id, 100, &[&match_id]);
+    let signature = sign_free_bet(&client, &voucher);
+    offer_odds(&client, &match_id, &home, 250);
+    let bet_id = client.place_free_bet(&bettor, &voucher, &signature, &match_id, &home, &250);
+    assert_eq!(client.get_match_exposure(&match_id), 150);
+    assert_eq!(client.get_wager_totals(&bettor).monthly, 100);
+
+    assert!(client.is_voucher_redeemed(&voucher.voucher_id));
+    assert!(client.get_bet(&bet_id).free_bet);
+    assert_eq!(
+        client.get_free_bet_voucher(&bet_id),
+        Some(voucher.voucher_id.clone())
+    );
+    assert_eq!(token_client.balance(&client.address), 5_000);
+
+    settle_match(&client, &admin, &match_id, &[(&home, SelectionResult::Won)]);
+    assert_eq!(client.claim(&bettor, &bet_id), 150);
+    assert_eq!(token_client.balance(&bettor), 150);
+    assert_eq!(client.get_bet(&bet_id).state, BetState::Won);
+    assert_eq!(client.get_match_exposure(&match_id), 0);
+}
+
+#[test]
+fn test_losing_free_bet_sweeps_nothing() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = register_token(&env, &client, &admin);
+    let token_client = token::Client::new(&env, &token_id);
+    token::StellarAssetClient::new(&env, &token_id).mint(&client.address, &5_000);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    let voucher = free_bet_voucher(&env, &bettor, &token_id, 100, &[]);
+    let signature = sign_free_bet(&client, &voucher);
+    offer_odds(&client, &match_id, &home, 250);
+    let bet_id = client.place_free_bet(&bettor, &voucher, &signature, &match_id, &home, &250);
+    assert_eq!(
+        client.try_cancel_bet(&bettor, &bet_id),
+        Err(Ok(ContractError::InvalidBet))
+    );
+
+    settle_match(
+        &client,
+        &admin,
+        &match_id,
+        &[(&home, SelectionResult::Lost)],
+    );
+    assert_eq!(
+        client.try_claim(&bettor, &bet_id),
+        Err(Ok(ContractError::NotClaimable))
+    );
+    client.sweep_losing_stakes(&match_id, &10);
+
+    assert_eq!(client.get_bet(&bet_id).state, BetState::Lost);
+    assert_eq!(token_client.balance(&treasury), 0);
+    assert_eq!(token_client.balance(&client.address), 5_000);
+    assert_eq!(client.get_wager_totals(&bettor).monthly_loss, 0);
+}
+
+#[test]
+fn test_free_bet_voucher_is_single_use_and_market_bound() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = register_token(&env, &client, &admin);
+    let eligible_match = BytesN::from_array(&env, &[1u8; 32]);
+    let other_match = BytesN::from_array(&env, &[2u8; 32]);
+    create_open_match(&env, &client, &admin, &eligible_match);
+    create_open_match(&env, &client, &admin, &other_match);
+    let home = Symbol::new(&env, "home");
+
+    let voucher = free_bet_voucher(&env, &bettor, &token_id, 100, &[&eligible_match]);
+    let signature = sign_free_bet(&client, &voucher);
+    offer_odds(&client, &other_match, &home, 200);
+    assert_eq!(
+        client.try_place_free_bet(&bettor, &voucher, &signature, &other_match, &home, &200),
+        Err(Ok(ContractError::InvalidBet))
+    );
+    let stranger = Address::generate(&env);
+    offer_odds(&client, &eligible_match, &home, 200);
+    assert_eq!(
+        client.try_place_free_bet(
+            &stranger,
+            &voucher,
+            &signature,
+            &eligible_match,
+            &home,
+            &200
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    offer_odds(&client, &eligible_match, &home, 200);
+    client.place_free_bet(&bettor, &voucher, &signature, &eligible_match, &home, &200);
+    assert_eq!(
+        client.try_place_free_bet(&bettor, &voucher, &signature, &eligible_match, &home, &200),
+        Err(Ok(ContractError::DuplicateOperation))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_free_bet_rejects_tampered_voucher() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = register_token(&env, &client, &admin);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+
+    let voucher = free_bet_voucher(&env, &bettor, &token_id, 100, &[]);
+    let signature = sign_free_bet(&client, &voucher);
+    let tampered = free_bet_voucher(&env, &bettor, &token_id, 10_000, &[]);
+    offer_odds(&client, &match_id, &Symbol::new(&env, "home"), 200);
+    client.place_free_bet(
+        &bettor,
+        &tampered,
+        &signature,
+        &match_id,
+        &Symbol::new(&env, "home"),
+        &200,
+    );
+}
+
+fn setup_pool(
+    env: &Env,
+) -> (
+    BettingContractClient<'_>,
+    Address,
+    Address,
+    BytesN<32>,
+    soroban_sdk::Vec<Symbol>,
+) {
+    let (client, admin, _) = setup_test(env);
+    let token_id = register_token(env, &client, &admin);
+    let match_id = BytesN::from_array(env, &[1u8; 32]);
+    create_open_match(env, &client, &admin, &match_id);
+    let outcomes = soroban_sdk::vec![
+        env,
+        Symbol::new(env, "home"),
+        Symbol::new(env, "away"),
+        Symbol::new(env, "draw"),
+    ];
+    client.create_pool(&admin, &match_id, &token_id, &outcomes, &500);
+    (client, admin, token_id, match_id, outcomes)
+}
+
+fn pool_bettor(env: &Env, token_id: &Address, amount: i128) -> Address {
+    let bettor = Address::generate(env);
+    token::StellarAssetClient::new(env, token_id).mint(&bettor, &amount);
+    bettor
+}
+
+#[test]
+fn test_pool_splits_pot_pro_rata_after_house_take() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, token_id, match_id, outcomes) = setup_pool(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    let home = outcomes.get(0).unwrap();
+    let away = outcomes.get(1).unwrap();
+
+    let first = pool_bettor(&env, &token_id, 100);
+    let second = pool_bettor(&env, &token_id, 200);
+    let third = pool_bettor(&env, &token_id, 400);
+    client.place_pool_bet(&first, &match_id, &home, &100);
+    client.place_pool_bet(&second, &match_id, &home, &200);
+    client.place_pool_bet(&third, &match_id, &away, &400);
+
+    assert_eq!(client.get_pool_outcome_total(&match_id, &home), 300);
+    assert_eq!(client.get_pool_outcome_total(&match_id, &away), 400);
+    assert_eq!(client.get_pool_stake(&match_id, &home, &second), 200);
+
+    // 700 staked, 5% take leaves 665 for winners
+    assert_eq!(client.get_pool_odds(&match_id, &home), 221);
+    assert_eq!(client.get_pool_odds(&match_id, &away), 166);
+    assert_eq!(
+        client.get_pool_odds(&match_id, &outcomes.get(2).unwrap()),
+        0
+    );
+
+    client.update_match_status(&admin, &match_id, &MatchStatus::Locked);
+    let pool = client.settle_pool(&admin, &match_id, &home);
+    assert_eq!(pool.status, PoolStatus::Settled);
+    assert_eq!(pool.net_pot, 665);
+    assert_eq!(token_client.balance(&treasury), 35);
+    // Losing stakes are recorded against the loss limit one bettor at a time
+    assert_eq!(client.get_wager_totals(&third).monthly_loss, 0);
+    assert_eq!(
+        client.try_record_pool_loss(&match_id, &first),
+        Err(Ok(ContractError::NotClaimable))
+    );
+    assert_eq!(client.record_pool_loss(&match_id, &third), 400);
+    assert_eq!(client.get_wager_totals(&third).monthly_loss, 400);
+    assert_eq!(
+        client.try_record_pool_loss(&match_id, &third),
+        Err(Ok(ContractError::NotClaimable))
+    );
+
+    assert_eq!(
+        client.try_claim_pool(&third, &match_id),
+        Err(Ok(ContractError::NotClaimable))
+    );
+    assert_eq!(client.claim_pool(&first, &match_id), 221);
+    assert_eq!(client.claim_pool(&second, &match_id), 443);
+    assert_eq!(client.get_wager_totals(&first).monthly_loss, -121);
+    assert_eq!(client.get_wager_totals(&second).monthly_loss, -243);
+    assert_eq!(
+        client.try_claim_pool(&second, &match_id),
+        Err(Ok(ContractError::NotClaimable))
+    );
+
+    // Rounding dust goes to the treasury once every winner has claimed
+    assert_eq!(token_client.balance(&treasury), 36);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_pool_without_winning_stake_refunds_everyone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, token_id, match_id, outcomes) = setup_pool(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let bettor = pool_bettor(&env, &token_id, 300);
+    client.place_pool_bet(&bettor, &match_id, &outcomes.get(0).unwrap(), &100);
+    client.place_pool_bet(&bettor, &match_id, &outcomes.get(1).unwrap(), &200);
+
+    assert_eq!(
+        client.try_claim_pool(&bettor, &match_id),
+        Err(Ok(ContractError::ResultNotFinal))
+    );
+    assert_eq!(
+        client.try_record_pool_loss(&match_id, &bettor),
+        Err(Ok(ContractError::ResultNotFinal))
+    );
+
+    client.update_match_status(&admin, &match_id, &MatchStatus::Locked);
+    let pool = client.settle_pool(&admin, &match_id, &outcomes.get(2).unwrap());
+    assert_eq!(pool.status, PoolStatus::Void);
+
+    assert_eq!(client.claim_pool(&bettor, &match_id), 300);
+    assert_eq!(token_client.balance(&bettor), 300);
+    assert_eq!(client.get_wager_totals(&bettor).monthly_loss, 0);
+}
+
+#[test]
+fn test_pool_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, token_id, match_id, outcomes) = setup_pool(&env);
+    let bettor = pool_bettor(&env, &token_id, 100);
+
+    assert_eq!(
+        client.try_create_pool(&admin, &match_id, &token_id, &outcomes, &500),
+        Err(Ok(ContractError::PoolAlreadyExists))
+    );
+
+    let other_match = BytesN::from_array(&env, &[2u8; 32]);
+    create_open_match(&env, &client, &admin, &other_match);
+    let home = Symbol::new(&env, "home");
+    assert_eq!(
+        client.try_create_pool(
+            &admin,
+            &other_match,
+            &token_id,
+            &soroban_sdk::vec![&env, home.clone(), home.clone()],
+            &500
+        ),
+        Err(Ok(ContractError::InvalidBet))
+    );
+    assert_eq!(
+        client.try_create_pool(&admin, &other_match, &token_id, &outcomes, &10_001),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_create_pool(
+            &admin,
+            &other_match,
+            &Address::generate(&env),
+            &outcomes,
+            &500
+        ),
+        Err(Ok(ContractError::TokenNotAllowed))
+    );
+
+    assert_eq!(
+        client.try_place_pool_bet(&bettor, &match_id, &Symbol::new(&env, "nobody"), &100),
+        Err(Ok(ContractError::InvalidBet))
+    );
+    assert_eq!(
+        client.try_place_pool_bet(&bettor, &other_match, &home, &100),
+        Err(Ok(ContractError::PoolNotFound))
+    );
+
+    // Pools settle only once betting has closed
+    assert_eq!(
+        client.try_settle_pool(&admin, &match_id, &home),
+        Err(Ok(ContractError::ResultNotFinal))
+    );
+}
+
+fn gaming_limits(daily: i128, weekly: i128, monthly: i128, loss: i128) -> GamingLimits {
+    GamingLimits {
+        daily_wager: daily,
+        weekly_wager: weekly,
+        monthly_wager: monthly,
+        monthly_loss: loss,
+    }
+}
+
+#[test]
+fn test_wager_caps_limit_stakes_per_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 10_000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.create_match(&admin, &match_id, &1_000, &(30 * 86_400));
+    client.update_match_status(&admin, &match_id, &MatchStatus::Open);
+    let home = Symbol::new(&env, "home");
+
+    assert_eq!(
+        client.set_gaming_limits(&bettor, &gaming_limits(500, 800, 0, 0)),
+        None
+    );
+
+    offer_odds(&client, &match_id, &home, 200);
+    client.place_bet(&bettor, &token_id, &300, &match_id, &home, &200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &300, &match_id, &home, &200),
+        Err(Ok(ContractError::WagerLimitExceeded))
+    );
+    assert_eq!(client.get_wager_totals(&bettor).daily, 300);
+
+    // The daily cap resets the next day, the weekly cap still applies
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    offer_odds(&client, &match_id, &home, 200);
+    client.place_bet(&bettor, &token_id, &400, &match_id, &home, &200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &200, &match_id, &home, &200),
+        Err(Ok(ContractError::WagerLimitExceeded))
+    );
+    let totals = client.get_wager_totals(&bettor);
+    assert_eq!(totals.daily, 400);
+    assert_eq!(totals.weekly, 700);
+}
+
+#[test]
+fn test_loosening_gaming_limits_waits_for_cooling_off() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+
+    client.set_gaming_limits(&bettor, &gaming_limits(500, 0, 0, 1_000));
+
+    // Raising the daily cap and removing the loss cap are both loosening
+    let requested = gaming_limits(1_000, 0, 0, 0);
+    let pending = client.set_gaming_limits(&bettor, &requested).unwrap();
+    assert_eq!(pending.limits, requested);
+    assert_eq!(pending.effective_at, env.ledger().timestamp() + 86_400);
+    assert_eq!(
+        client.get_gaming_limits(&bettor),
+        gaming_limits(500, 0, 0, 1_000)
+    );
+
+    // Tightening still applies right away alongside the pending request
+    client.set_gaming_limits(&bettor, &gaming_limits(1_000, 2_000, 0, 0));
+    assert_eq!(
+        client.get_gaming_limits(&bettor),
+        gaming_limits(500, 2_000, 0, 1_000)
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(
+        client.get_gaming_limits(&bettor),
+        gaming_limits(1_000, 2_000, 0, 0)
+    );
+    assert_eq!(client.get_pending_gaming_limits(&bettor), None);
+
+    assert_eq!(
+        client.try_set_gaming_limits(&bettor, &gaming_limits(-1, 0, 0, 0)),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_loss_cap_counts_settled_losses() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1_000);
+    client.set_treasury(&admin, &Address::generate(&env));
+    client.set_gaming_limits(&bettor, &gaming_limits(0, 0, 0, 500));
+    let home = Symbol::new(&env, "home");
+
+    let first_match = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &first_match);
+    offer_odds(&client, &first_match, &home, 200);
+    client.place_bet(&bettor, &token_id, &400, &first_match, &home, &200);
+    settle_match(
+        &client,
+        &admin,
+        &first_match,
+        &[(&home, SelectionResult::Lost)],
+    );
+    client.sweep_losing_stakes(&first_match, &10);
+    assert_eq!(client.get_wager_totals(&bettor).monthly_loss, 400);
+
+    let second_match = BytesN::from_array(&env, &[2u8; 32]);
+    create_open_match(&env, &client, &admin, &second_match);
+    offer_odds(&client, &second_match, &home, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &200, &second_match, &home, &200),
+        Err(Ok(ContractError::LossLimitExceeded))
+    );
+    offer_odds(&client, &second_match, &home, 200);
+    client.place_bet(&bettor, &token_id, &100, &second_match, &home, &200);
+}
+
+#[test]
+fn test_self_exclusion_blocks_bets_and_spins() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1_000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.create_match(&admin, &match_id, &1_000, &100_000);
+    client.update_match_status(&admin, &match_id, &MatchStatus::Open);
+    let home = Symbol::new(&env, "home");
+
+    assert_eq!(client.self_exclude(&bettor, &10_000), 11_000);
+    // A shorter exclusion does not shorten the current one
+    assert_eq!(client.self_exclude(&bettor, &10), 11_000);
+    assert_eq!(client.get_self_exclusion(&bettor), 11_000);
+
+    offer_odds(&client, &match_id, &home, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &home, &200),
+        Err(Ok(ContractError::SelfExcluded))
+    );
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &bettor);
+    assert_eq!(
+        client.try_execute_spin(&spin_id, &spin_hash, &signature, &bettor, &SPIN_EXPIRY),
+        Err(Ok(ContractError::SelfExcluded))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 11_000);
+    offer_odds(&client, &match_id, &home, 200);
+    client.place_bet(&bettor, &token_id, &100, &match_id, &home, &200);
+    client.execute_spin(&spin_id, &spin_hash, &signature, &bettor, &SPIN_EXPIRY);
+}
+
+#[test]
+fn test_guardian_pauses_scopes_independently() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    offer_odds(&client, &match_id, &home, 200);
+    let bet_id = client.place_bet(&bettor, &token_id, &100, &match_id, &home, &200);
+
+    let guardian = Address::generate(&env);
+    assert_eq!(
+        client.try_set_guardian(&admin, &admin),
+        Err(Ok(ContractError::InvalidGuardian))
+    );
+    client.set_guardian(&admin, &guardian);
+    assert_eq!(client.get_guardian(), Some(guardian.clone()));
+
+    // Only the guardian can pause, the backend signer cannot
+    assert_eq!(
+        client.try_pause(&admin, &PauseScope::Bets),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.pause(&guardian, &PauseScope::Bets);
+    assert!(client.is_paused(&PauseScope::Bets));
+    assert!(!client.is_paused(&PauseScope::Withdrawals));
+    offer_odds(&client, &match_id, &home, 200);
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &home, &200),
+        Err(Ok(ContractError::Paused))
+    );
+    // Other scopes keep working
+    client.cancel_bet(&bettor, &bet_id);
+
+    client.pause(&guardian, &PauseScope::Spins);
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &bettor);
+    assert_eq!(
+        client.try_execute_spin(&spin_id, &spin_hash, &signature, &bettor, &SPIN_EXPIRY),
+        Err(Ok(ContractError::Paused))
+    );
+
+    client.unpause(&guardian, &PauseScope::Bets);
+    assert!(!client.is_paused(&PauseScope::Bets));
+    offer_odds(&client, &match_id, &home, 200);
+    client.place_bet(&bettor, &token_id, &100, &match_id, &home, &200);
+}
+
+#[test]
+fn test_withdrawals_pause_blocks_payouts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    token::StellarAssetClient::new(&env, &token_id).mint(&client.address, &1_000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    offer_odds(&client, &match_id, &home, 200);
+    let bet_id = client.place_bet(&bettor, &token_id, &100, &match_id, &home, &200);
+    settle_match(&client, &admin, &match_id, &[(&home, SelectionResult::Won)]);
+
+    let guardian = Address::generate(&env);
+    client.set_guardian(&admin, &guardian);
+    client.pause(&guardian, &PauseScope::Withdrawals);
+    assert_eq!(
+        client.try_claim(&bettor, &bet_id),
+        Err(Ok(ContractError::Paused))
+    );
+
+    client.unpause(&guardian, &PauseScope::Withdrawals);
+    assert_eq!(client.claim(&bettor, &bet_id), 200);
+}
+
+#[test]
+fn test_live_bet_is_repriced_within_slippage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    assert_eq!(
+        client.try_place_live_bet(&bettor, &token_id, &100, &match_id, &home, &200, &0),
+        Err(Ok(ContractError::OddsStale))
+    );
+
+    let publication = publish_odds(&client, &match_id, &home, 195, 1);
+    assert_eq!(client.get_live_odds(&match_id, &home), Some(publication));
+
+    // 200 -> 195 is a 2.5% move: inside 3%, outside 2%
+    assert_eq!(
+        client.try_place_live_bet(&bettor, &token_id, &100, &match_id, &home, &200, &200),
+        Err(Ok(ContractError::OddsMoved))
+    );
+    let bet_id = client.place_live_bet(&bettor, &token_id, &100, &match_id, &home, &200, &300);
+    assert_eq!(client.get_bet(&bet_id).odds, 195);
+
+    // Moves in the bettor's favour are always taken at the current price
+    publish_odds(&client, &match_id, &home, 210, 2);
+    let bet_id = client.place_live_bet(&bettor, &token_id, &100, &match_id, &home, &200, &0);
+    assert_eq!(client.get_bet(&bet_id).odds, 210);
+
+    // Plain bets on a live selection accept no slippage
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &home, &220),
+        Err(Ok(ContractError::OddsMoved))
+    );
+}
+
+#[test]
+fn test_stale_odds_are_refused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+
+    client.set_odds_staleness(&admin, &10);
+    assert_eq!(client.get_odds_staleness(), 10);
+    let publication = publish_odds(&client, &match_id, &home, 200, 5);
+
+    // Versions only move forward
+    let mut older = publication.clone();
+    older.version = 4;
+    let message: std::vec::Vec<u8> = client.odds_signing_payload(&older).iter().collect();
+    let signature = BytesN::from_array(&env, &backend_key().sign(&message).to_bytes());
+    assert_eq!(
+        client.try_publish_odds(&older, &signature),
+        Err(Ok(ContractError::OddsStale))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 11);
+    assert_eq!(
+        client.try_place_live_bet(&bettor, &token_id, &100, &match_id, &home, &200, &100),
+        Err(Ok(ContractError::OddsStale))
+    );
+    // A pre-match price stays valid until it is superseded
+    client.place_bet(&bettor, &token_id, &100, &match_id, &home, &200);
+
+    // A publication that was already too old when relayed is refused
+    let mut late = publication;
+    late.version = 6;
+    let message: std::vec::Vec<u8> = client.odds_signing_payload(&late).iter().collect();
+    let signature = BytesN::from_array(&env, &backend_key().sign(&message).to_bytes());
+    assert_eq!(
+        client.try_publish_odds(&late, &signature),
+        Err(Ok(ContractError::OddsStale))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_publish_odds_with_forged_signature_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _) = setup_test(&env);
+    let publication = OddsPublication {
+        match_id: BytesN::from_array(&env, &[1u8; 32]),
+        bet_type: Symbol::new(&env, "home"),
+        odds: 500,
+        version: 1,
+        published_at: env.ledger().timestamp(),
+    };
+    let forger = SigningKey::from_bytes(&[7u8; 32]);
+    let message: std::vec::Vec<u8> = client.odds_signing_payload(&publication).iter().collect();
+    let signature = BytesN::from_array(&env, &forger.sign(&message).to_bytes());
+    client.publish_odds(&publication, &signature);
+}
+
+fn setup_accumulator(
+    env: &Env,
+    legs: u8,
+) -> (
+    BettingContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    soroban_sdk::Vec<Selection>,
+) {
+    let (client, admin, bettor) = setup_test(env);
+    let token_id = register_token(env, &client, &admin);
+    let token_admin_client = token::StellarAssetClient::new(env, &token_id);
+    token_admin_client.mint(&bettor, &1000);
+    // House bankroll available for winnings
+    token_admin_client.mint(&client.address, &100_000);
+
+    let mut selections = soroban_sdk::Vec::new(env);
+    for i in 0..legs {
+        let match_id = BytesN::from_array(env, &[i + 1; 32]);
+        create_open_match(env, &client, &admin, &match_id);
+        let bet_type = Symbol::new(env, "home");
+        offer_odds(&client, &match_id, &bet_type, 200);
+        selections.push_back(Selection {
+            match_id,
+            bet_type,
+            odds: 200,
+        });
+    }
+    (client, admin, bettor, token_id, selections)
+}
+
+#[test]
+fn test_accumulator_pays_when_every_leg_wins() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor, token_id, selections) = setup_accumulator(&env, 3);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let acca_id = client.place_accumulator(&bettor, &token_id, &100, &selections);
+    let acca = client.get_accumulator(&acca_id);
+    assert_eq!(acca.combined_odds, 800);
+    assert_eq!(acca.state, BetState::Active);
+    assert_eq!(client.get_user_accumulators(&bettor).len(), 1);
+
+    let home = Symbol::new(&env, "home");
+    for (i, selection) in selections.iter().enumerate() {
+        settle_match(
+            &client,
+            &admin,
+            &selection.match_id,
+            &[(&home, SelectionResult::Won)],
+        );
+        let acca = client.resolve_accumulator(&acca_id);
+        if i < 2 {
+            // Not paid until every leg is decided
+            assert_eq!(acca.state, BetState::Active);
+        } else {
+            assert_eq!(acca.state, BetState::Won);
+            assert_eq!(acca.payout, 800);
+        }
+    }
+
+    assert_eq!(token_client.balance(&bettor), 900 + 800);
+    assert!(client.get_user_accumulators(&bettor).is_empty());
+    assert_eq!(
+        client.try_resolve_accumulator(&acca_id),
+        Err(Ok(ContractError::BetAlreadySettled))
+    );
+}
+
+#[test]
+fn test_accumulator_settles_as_soon_as_one_leg_loses() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor, token_id, selections) = setup_accumulator(&env, 3);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let acca_id = client.place_accumulator(&bettor, &token_id, &100, &selections);
+
+    let first = selections.get(0).unwrap();
+    settle_match(
+        &client,
+        &admin,
+        &first.match_id,
+        &[(&first.bet_type, SelectionResult::Lost)],
+    );
+
+    let acca = client.resolve_accumulator(&acca_id);
+    assert_eq!(acca.state, BetState::Lost);
+    assert_eq!(acca.payout, 0);
+    assert_eq!(acca.legs.get(1).unwrap().result, SelectionResult::Pending);
+    assert_eq!(token_client.balance(&bettor), 900);
+}
+
+#[test]
+fn test_accumulator_drops_void_legs_from_odds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor, token_id, selections) = setup_accumulator(&env, 3);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let acca_id = client.place_accumulator(&bettor, &token_id, &100, &selections);
+
+    // One match is abandoned, the other two legs win
+    client.void_match(&admin, &selections.get(0).unwrap().match_id, &10);
+    for selection in selections.iter().skip(1) {
+        settle_match(
+            &client,
+            &admin,
+            &selection.match_id,
+            &[(&selection.bet_type, SelectionResult::Won)],
+        );
+    }
+
+    let acca = client.resolve_accumulator(&acca_id);
+    assert_eq!(acca.state, BetState::Won);
+    assert_eq!(acca.combined_odds, 400);
+    assert_eq!(acca.payout, 400);
+    assert_eq!(acca.legs.get(0).unwrap().result, SelectionResult::Void);
+    assert_eq!(token_client.balance(&bettor), 900 + 400);
+}
+
+#[test]
+fn test_accumulator_with_all_legs_void_refunds_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor, token_id, selections) = setup_accumulator(&env, 2);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let acca_id = client.place_accumulator(&bettor, &token_id, &100, &selections);
+    for selection in selections.iter() {
+        settle_match(
+            &client,
+            &admin,
+            &selection.match_id,
+            &[(&selection.bet_type, SelectionResult::Void)],
+        );
+    }
+
+    let acca = client.resolve_accumulator(&acca_id);
+    assert_eq!(acca.state, BetState::Void);
+    assert_eq!(acca.payout, 100);
+    assert_eq!(token_client.balance(&bettor), 1000);
+}
+
+#[test]
+fn test_accumulator_waits_for_settled_matches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor, token_id, selections) = setup_accumulator(&env, 2);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let acca_id = client.place_accumulator(&bettor, &token_id, &100, &selections);
+
+    // Results can only be recorded once betting on the match has closed
+    let first = selections.get(0).unwrap();
+    assert_eq!(
+        client.try_set_selection_result(
+            &admin,
+            &first.match_id,
+            &first.bet_type,
+            &SelectionResult::Won
+        ),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+
+    // Results recorded before the matches are settled do not decide legs
+    for selection in selections.iter() {
+        client.update_match_status(&admin, &selection.match_id, &MatchStatus::Locked);
+        client.set_selection_result(
+            &admin,
+            &selection.match_id,
+            &selection.bet_type,
+            &SelectionResult::Won,
+        );
+    }
+    let acca = client.resolve_accumulator(&acca_id);
+    assert_eq!(acca.state, BetState::Active);
+    assert_eq!(token_client.balance(&bettor), 900);
+
+    for selection in selections.iter() {
+        client.update_match_status(&admin, &selection.match_id, &MatchStatus::Settled);
+    }
+    let acca = client.resolve_accumulator(&acca_id);
+    assert_eq!(acca.state, BetState::Won);
+    assert_eq!(acca.payout, 400);
+}
+
+#[test]
+fn test_accumulator_legs_are_priced_and_count_toward_liability() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor, token_id, selections) = setup_accumulator(&env, 2);
+    let first = selections.get(0).unwrap();
+    let second = selections.get(1).unwrap();
+
+    // A leg requested above the house price is rejected
+    let mut greedy = selections.clone();
+    greedy.set(
+        0,
+        Selection {
+            odds: 300,
+            ..first.clone()
+        },
+    );
+    assert_eq!(
+        client.try_place_accumulator(&bettor, &token_id, &100, &greedy),
+        Err(Ok(ContractError::OddsMoved))
+    );
+
+    // Legs are taken at the current price when it beats the requested odds
+    offer_odds(&client, &first.match_id, &first.bet_type, 250);
+    client.set_liability_limits(&admin, &second.match_id, &0, &600);
+    let acca_id = client.place_accumulator(&bettor, &token_id, &100, &selections);
+    let acca = client.get_accumulator(&acca_id);
+    assert_eq!(acca.legs.get(0).unwrap().odds, 250);
+    assert_eq!(acca.combined_odds, 500);
+    assert_eq!(client.get_match_exposure(&first.match_id), 500);
+    assert_eq!(
+        client.get_selection_exposure(&second.match_id, &second.bet_type),
+        500
+    );
+
+    // A second accumulator would take the selection over its limit
+    assert_eq!(
+        client.try_place_accumulator(&bettor, &token_id, &100, &selections),
+        Err(Ok(ContractError::LiabilityExceeded))
+    );
+
+    // Settling the accumulator releases its exposure
+    settle_match(
+        &client,
+        &admin,
+        &first.match_id,
+        &[(&first.bet_type, SelectionResult::Lost)],
+    );
+    assert_eq!(client.resolve_accumulator(&acca_id).state, BetState::Lost);
+    assert_eq!(client.get_match_exposure(&first.match_id), 0);
+    assert_eq!(
+        client.get_selection_exposure(&second.match_id, &second.bet_type),
+        0
+    );
+}
+
+#[test]
+fn test_accumulator_rejects_invalid_selections() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor, token_id, selections) = setup_accumulator(&env, 2);
 
-    let match_id = BytesN::from_array(&env, &[1u8; 32]);
-    let bet_type = Symbol::new(&env, "win");
-    let odds = 200;
+    // A single selection is not an accumulator
+    let mut single = soroban_sdk::Vec::new(&env);
+    single.push_back(selections.get(0).unwrap());
+    assert_eq!(
+        client.try_place_accumulator(&bettor, &token_id, &100, &single),
+        Err(Ok(ContractError::InvalidBet))
+    );
 
-    // Ensure double betting is allowed (default)
-    assert!(!client.is_double_betting_prevented());
+    // Two legs on the same match
+    let mut same_match = soroban_sdk::Vec::new(&env);
+    same_match.push_back(selections.get(0).unwrap());
+    same_match.push_back(selections.get(0).unwrap());
+    assert_eq!(
+        client.try_place_accumulator(&bettor, &token_id, &100, &same_match),
+        Err(Ok(ContractError::InvalidBet))
+    );
 
-    // First bet
-    client.place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
+    // More than the maximum number of legs
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for i in 0..13u8 {
+        too_many.push_back(Selection {
+            match_id: BytesN::from_array(&env, &[100 + i; 32]),
+            bet_type: Symbol::new(&env, "home"),
+            odds: 200,
+        });
+    }
+    assert_eq!(
+        client.try_place_accumulator(&bettor, &token_id, &100, &too_many),
+        Err(Ok(ContractError::InvalidBet))
+    );
 
-    // Second bet on same match by same user should succeed
-    let result = client.try_place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
-    assert!(result.is_ok());
+    // Legs on unknown matches
+    let mut unknown = soroban_sdk::Vec::new(&env);
+    for i in 0..2u8 {
+        unknown.push_back(Selection {
+            match_id: BytesN::from_array(&env, &[200 + i; 32]),
+            bet_type: Symbol::new(&env, "home"),
+            odds: 200,
+        });
+    }
+    assert_eq!(
+        client.try_place_accumulator(&bettor, &token_id, &100, &unknown),
+        Err(Ok(ContractError::MatchNotFound))
+    );
 }
 
 #[test]
@@ -110,11 +2394,11 @@ fn executes_spin_once_per_spin_id() {
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
 
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
+    client.execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
     assert_eq!(
-        client.try_execute_spin(&spin_id, &spin_hash, &signature, &executor),
+        client.try_execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY),
         Err(Ok(ContractError::DuplicateOperation))
     );
 }
@@ -126,22 +2410,25 @@ fn rejects_replay_by_spin_hash() {
 
     let (client, _, executor) = setup_test(&env);
 
+    let spin_id1 = BytesN::from_array(&env, &[7u8; 32]);
+    let spin_id2 = BytesN::from_array(&env, &[8u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[9u8; 32]);
-    let signature = BytesN::from_array(&env, &[4u8; 64]);
 
     client.execute_spin(
-        &BytesN::from_array(&env, &[7u8; 32]),
+        &spin_id1,
         &spin_hash,
-        &signature,
+        &sign_spin(&client, &spin_id1, &spin_hash, &executor),
         &executor,
+        &SPIN_EXPIRY,
     );
 
     assert_eq!(
         client.try_execute_spin(
-            &BytesN::from_array(&env, &[8u8; 32]),
+            &spin_id2,
             &spin_hash,
-            &signature,
+            &sign_spin(&client, &spin_id2, &spin_hash, &executor),
             &executor,
+            &SPIN_EXPIRY,
         ),
         Err(Ok(ContractError::DuplicateOperation))
     );
@@ -156,10 +2443,10 @@ fn reports_spin_hash_usage() {
 
     let spin_id = BytesN::from_array(&env, &[10u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[11u8; 32]);
-    let signature = BytesN::from_array(&env, &[5u8; 64]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
 
     assert!(!client.is_spin_hash_used(&spin_hash));
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
+    client.execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
     assert!(client.is_spin_hash_used(&spin_hash));
 }
 
@@ -172,9 +2459,16 @@ fn supports_ttl_cleanup_for_spin_hashes() {
 
     let spin_id = BytesN::from_array(&env, &[12u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[13u8; 32]);
-    let signature = BytesN::from_array(&env, &[6u8; 64]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
 
-    client.execute_spin_with_ttl(&spin_id, &spin_hash, &signature, &executor, &Some(5));
+    client.execute_spin_with_ttl(
+        &spin_id,
+        &spin_hash,
+        &signature,
+        &executor,
+        &SPIN_EXPIRY,
+        &Some(5),
+    );
     assert!(client.is_spin_hash_used(&spin_hash));
 
     env.ledger().with_mut(|li| {
@@ -185,27 +2479,377 @@ fn supports_ttl_cleanup_for_spin_hashes() {
     assert!(!client.is_spin_hash_used(&spin_hash));
 }
 
+#[test]
+fn stores_each_spin_under_its_own_key_with_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[14u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[15u8; 32]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
+    client.execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
+
+    assert_eq!(client.get_spin_execution(&spin_id).executor, executor);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::SpinExecutions));
+        assert!(
+            storage.get_ttl(&DataKey::SpinExecution(spin_id.clone())) >= spins::SPIN_TTL_THRESHOLD
+        );
+    });
+}
+
+#[test]
+fn migrates_legacy_spin_executions_in_batches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, executor) = setup_test(&env);
+
+    let mut legacy = soroban_sdk::Map::new(&env);
+    for seed in 1..=3u8 {
+        let spin_id = BytesN::from_array(&env, &[seed; 32]);
+        legacy.set(
+            spin_id.clone(),
+            SpinExecution {
+                spin_id,
+                executor: executor.clone(),
+                timestamp: seed as u64,
+            },
+        );
+    }
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::SpinExecutions, &legacy);
+    });
+
+    // Legacy executions still block a replay before they are migrated
+    let spin_id = BytesN::from_array(&env, &[2u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[20u8; 32]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
+    assert_eq!(
+        client.try_execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY),
+        Err(Ok(ContractError::SpinAlreadyExecuted))
+    );
+
+    assert_eq!(client.migrate_spin_executions(&admin, &2), 1);
+    assert_eq!(client.migrate_spin_executions(&admin, &2), 0);
+    assert_eq!(client.migrate_spin_executions(&admin, &2), 0);
+
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::SpinExecutions));
+    });
+    for seed in 1..=3u8 {
+        let execution = client.get_spin_execution(&BytesN::from_array(&env, &[seed; 32]));
+        assert_eq!(execution.timestamp, seed as u64);
+    }
+    assert_eq!(
+        client.try_migrate_spin_executions(&executor, &2),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
 // ============================================
-// Authorization Tests - Unauthorized Calls
+// Signature Verification Tests
 // ============================================
 
 #[test]
-#[should_panic]
-fn execute_spin_without_auth_fails() {
+fn executor_can_submit_backend_signed_spin_without_backend_auth() {
+    let env = Env::default();
+
+    let (client, _, executor) = setup_test_with_auths(&env);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
+
+    // Only the executor authorizes the transaction; the backend signed off-chain
+    client
+        .mock_auths(&[MockAuth {
+            address: &executor,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "execute_spin",
+                args: (
+                    spin_id.clone(),
+                    spin_hash.clone(),
+                    signature.clone(),
+                    executor.clone(),
+                    SPIN_EXPIRY,
+                )
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
+
+    assert!(client.is_spin_executed(&spin_id));
+}
+
+#[test]
+fn execute_spin_with_signature_from_wrong_key_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let forged = sign_spin_with(
+        &SigningKey::from_bytes(&[9u8; 32]),
+        &client,
+        &spin_id,
+        &spin_hash,
+        &executor,
+        SPIN_EXPIRY,
+    );
+
+    // The host aborts on a failed verification; no contract error is returned
+    assert_eq!(
+        client.try_execute_spin(&spin_id, &spin_hash, &forged, &executor, &SPIN_EXPIRY),
+        Err(Err(InvokeError::Abort))
+    );
+    assert!(!client.is_spin_executed(&spin_id));
+}
+
+#[test]
+fn execute_spin_with_signature_for_other_executor_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+    let other = Address::generate(&env);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
+
+    assert_eq!(
+        client.try_execute_spin(&spin_id, &spin_hash, &signature, &other, &SPIN_EXPIRY),
+        Err(Err(InvokeError::Abort))
+    );
+    assert!(!client.is_spin_hash_used(&spin_hash));
+}
+
+#[test]
+fn execute_spin_after_expiry_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let expires_at = 100;
+    let signature = sign_spin_with(
+        &backend_key(),
+        &client,
+        &spin_id,
+        &spin_hash,
+        &executor,
+        expires_at,
+    );
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at + 1;
+    });
+
+    let result = client.try_execute_spin(&spin_id, &spin_hash, &signature, &executor, &expires_at);
+    assert_eq!(result, Err(Ok(ContractError::SignatureExpired)));
+}
+
+#[test]
+fn execute_spin_without_public_key_fails() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let backend_signer = Address::generate(&env);
     let executor = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
+    let contract_id = env.register(
+        BettingContract,
+        (backend_signer.clone(), backend_signer.clone()),
+    );
+    let client = BettingContractClient::new(&env, &contract_id);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
+
+    let result = client.try_execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
+    assert_eq!(result, Err(Ok(ContractError::InvalidSignature)));
+}
+
+#[test]
+fn rotate_backend_signer_by_non_admin_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, stranger) = setup_test(&env);
+    let public_key = BytesN::from_array(&env, &[1u8; 32]);
+
+    let result = client.try_rotate_backend_signer(&stranger, &public_key);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn constructor_sets_admin_and_operator() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let contract_id = env.register(BettingContract, (admin.clone(), operator.clone()));
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    assert_eq!(client.get_admin(), Some(admin));
+    assert_eq!(client.get_operator(), Some(operator));
+}
+
+#[test]
+fn only_the_settlement_contract_marks_bets_settled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_id = fund_bettor(&env, &client, &admin, &bettor, 1000);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    create_open_match(&env, &client, &admin, &match_id);
+    let home = Symbol::new(&env, "home");
+    offer_odds(&client, &match_id, &home, 200);
+    let bet_id = client.place_bet(&bettor, &token_id, &100, &match_id, &home, &200);
+    let results = soroban_sdk::vec![&env, (bet_id.clone(), BetState::Lost, 0i128)];
+
+    let settlement = Address::generate(&env);
+    assert_eq!(
+        client.try_mark_settled(&settlement, &results),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_set_settlement_contract(&bettor, &settlement),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_settlement_contract(&admin, &settlement);
+    assert_eq!(client.get_settlement_contract(), Some(settlement.clone()));
+    assert_eq!(
+        client.try_mark_settled(&admin, &results),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // The escrowed stake needs a treasury to move to
+    assert_eq!(
+        client.try_mark_settled(&settlement, &results),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+
+    client.mark_settled(&settlement, &results);
+    assert_eq!(client.get_bet(&bet_id).state, BetState::Lost);
+    assert!(client.get_user_bets(&bettor).is_empty());
+    assert_eq!(client.get_match_exposure(&match_id), 0);
+    assert_eq!(client.get_wager_totals(&bettor).monthly_loss, 100);
+    let token_client = token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(token_client.balance(&treasury), 100);
+}
+
+#[test]
+fn admin_and_operator_roles_are_separate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let contract_id = env.register(BettingContract, (admin.clone(), operator.clone()));
+    let client = BettingContractClient::new(&env, &contract_id);
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        client.try_create_match(&admin, &match_id, &0, &3_600),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.create_match(&operator, &match_id, &0, &3_600);
+
+    assert_eq!(
+        client.try_set_prevent_double_betting(&operator, &true),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_prevent_double_betting(&admin, &true);
+
+    let new_operator = Address::generate(&env);
+    assert_eq!(
+        client.try_set_operator(&operator, &new_operator),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_operator(&admin, &new_operator);
+    assert_eq!(
+        client.try_update_match_status(&operator, &match_id, &MatchStatus::Open),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.update_match_status(&new_operator, &match_id, &MatchStatus::Open);
+}
+
+#[test]
+fn rotated_backend_signer_signs_spins() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, executor) = setup_test(&env);
+    let new_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &new_key.verifying_key().to_bytes());
+    client.rotate_backend_signer(&admin, &public_key);
+    assert_eq!(client.get_backend_public_key(), Some(public_key));
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let signature = sign_spin_with(
+        &new_key,
+        &client,
+        &spin_id,
+        &spin_hash,
+        &executor,
+        SPIN_EXPIRY,
+    );
+    client.execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
+    assert!(client.is_spin_executed(&spin_id));
+}
+
+#[test]
+#[should_panic]
+fn rotated_out_backend_signer_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, executor) = setup_test(&env);
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    // Signed by the original key before the rotation
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
+
+    let new_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &new_key.verifying_key().to_bytes());
+    client.rotate_backend_signer(&admin, &public_key);
+
+    client.execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
+}
+
+// ============================================
+// Authorization Tests - Unauthorized Calls
+// ============================================
+
+#[test]
+#[should_panic]
+fn execute_spin_without_auth_fails() {
+    let env = Env::default();
+    let (client, _, executor) = setup_test_with_auths(&env);
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
 
     // Don't mock any auths - should fail due to executor.require_auth()
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
+    client.execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
 }
 
 // ============================================
@@ -218,11 +2862,12 @@ fn get_spin_execution_for_nonexistent_spin_fails() {
     env.mock_all_auths();
 
     let backend_signer = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
+    let contract_id = env.register(
+        BettingContract,
+        (backend_signer.clone(), backend_signer.clone()),
+    );
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
-
     let nonexistent_spin_id = BytesN::from_array(&env, &[99u8; 32]);
 
     let result = client.try_get_spin_execution(&nonexistent_spin_id);
@@ -235,11 +2880,12 @@ fn is_spin_executed_returns_false_for_new_spin() {
     env.mock_all_auths();
 
     let backend_signer = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
+    let contract_id = env.register(
+        BettingContract,
+        (backend_signer.clone(), backend_signer.clone()),
+    );
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
-
     let new_spin_id = BytesN::from_array(&env, &[1u8; 32]);
 
     assert!(!client.is_spin_executed(&new_spin_id));
@@ -251,11 +2897,12 @@ fn is_spin_hash_used_returns_false_for_new_hash() {
     env.mock_all_auths();
 
     let backend_signer = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
+    let contract_id = env.register(
+        BettingContract,
+        (backend_signer.clone(), backend_signer.clone()),
+    );
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
-
     let new_spin_hash = BytesN::from_array(&env, &[1u8; 32]);
 
     assert!(!client.is_spin_hash_used(&new_spin_hash));
@@ -267,11 +2914,12 @@ fn cleanup_spin_hash_returns_false_for_nonexistent_hash() {
     env.mock_all_auths();
 
     let backend_signer = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
+    let contract_id = env.register(
+        BettingContract,
+        (backend_signer.clone(), backend_signer.clone()),
+    );
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
-
     let nonexistent_hash = BytesN::from_array(&env, &[99u8; 32]);
 
     assert!(!client.cleanup_spin_hash(&nonexistent_hash));
@@ -282,19 +2930,21 @@ fn cleanup_spin_hash_returns_false_before_ttl_expires() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let backend_signer = Address::generate(&env);
-    let executor = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend_signer);
+    let (client, _, executor) = setup_test(&env);
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
 
     // Execute with 100 second TTL
-    client.execute_spin_with_ttl(&spin_id, &spin_hash, &signature, &executor, &Some(100));
+    client.execute_spin_with_ttl(
+        &spin_id,
+        &spin_hash,
+        &signature,
+        &executor,
+        &SPIN_EXPIRY,
+        &Some(100),
+    );
 
     // Advance time but not past TTL
     env.ledger().with_mut(|li| {
@@ -311,23 +2961,31 @@ fn different_spin_ids_same_hash_replay_fails() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let backend_signer = Address::generate(&env);
-    let executor = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend_signer);
+    let (client, _, executor) = setup_test(&env);
 
     let spin_id1 = BytesN::from_array(&env, &[1u8; 32]);
     let spin_id2 = BytesN::from_array(&env, &[2u8; 32]);
     let same_spin_hash = BytesN::from_array(&env, &[3u8; 32]);
-    let signature = BytesN::from_array(&env, &[4u8; 64]);
 
     // First execution succeeds
-    client.execute_spin(&spin_id1, &same_spin_hash, &signature, &executor);
+    let signature1 = sign_spin(&client, &spin_id1, &same_spin_hash, &executor);
+    client.execute_spin(
+        &spin_id1,
+        &same_spin_hash,
+        &signature1,
+        &executor,
+        &SPIN_EXPIRY,
+    );
 
     // Second execution with different spin_id but same hash should fail
-    let result = client.try_execute_spin(&spin_id2, &same_spin_hash, &signature, &executor);
+    let signature2 = sign_spin(&client, &spin_id2, &same_spin_hash, &executor);
+    let result = client.try_execute_spin(
+        &spin_id2,
+        &same_spin_hash,
+        &signature2,
+        &executor,
+        &SPIN_EXPIRY,
+    );
     assert_eq!(result, Err(Ok(ContractError::DuplicateOperation)));
 }
 
@@ -336,23 +2994,31 @@ fn same_spin_id_different_hash_fails() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let backend_signer = Address::generate(&env);
-    let executor = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend_signer);
+    let (client, _, executor) = setup_test(&env);
 
     let same_spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash1 = BytesN::from_array(&env, &[2u8; 32]);
     let spin_hash2 = BytesN::from_array(&env, &[3u8; 32]);
-    let signature = BytesN::from_array(&env, &[4u8; 64]);
 
     // First execution succeeds
-    client.execute_spin(&same_spin_id, &spin_hash1, &signature, &executor);
+    let signature1 = sign_spin(&client, &same_spin_id, &spin_hash1, &executor);
+    client.execute_spin(
+        &same_spin_id,
+        &spin_hash1,
+        &signature1,
+        &executor,
+        &SPIN_EXPIRY,
+    );
 
     // Second execution with same spin_id but different hash should fail
-    let result = client.try_execute_spin(&same_spin_id, &spin_hash2, &signature, &executor);
+    let signature2 = sign_spin(&client, &same_spin_id, &spin_hash2, &executor);
+    let result = client.try_execute_spin(
+        &same_spin_id,
+        &spin_hash2,
+        &signature2,
+        &executor,
+        &SPIN_EXPIRY,
+    );
     assert_eq!(result, Err(Ok(ContractError::SpinAlreadyExecuted)));
 }
 
@@ -361,23 +3027,18 @@ fn get_spin_execution_returns_correct_data() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let backend_signer = Address::generate(&env);
-    let executor = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend_signer);
+    let (client, _, executor) = setup_test(&env);
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
 
     // Set a specific timestamp
     env.ledger().with_mut(|li| {
         li.timestamp = 12345;
     });
 
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
+    client.execute_spin(&spin_id, &spin_hash, &signature, &executor, &SPIN_EXPIRY);
 
     let execution = client.get_spin_execution(&spin_id);
     assert_eq!(execution.spin_id, spin_id);
@@ -390,23 +3051,31 @@ fn multiple_executors_isolated() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let backend_signer = Address::generate(&env);
-    let executor1 = Address::generate(&env);
+    let (client, _, executor1) = setup_test(&env);
     let executor2 = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend_signer);
 
     let spin_id1 = BytesN::from_array(&env, &[1u8; 32]);
     let spin_id2 = BytesN::from_array(&env, &[2u8; 32]);
     let spin_hash1 = BytesN::from_array(&env, &[3u8; 32]);
     let spin_hash2 = BytesN::from_array(&env, &[4u8; 32]);
-    let signature = BytesN::from_array(&env, &[5u8; 64]);
 
     // Both executors can execute spins
-    client.execute_spin(&spin_id1, &spin_hash1, &signature, &executor1);
-    client.execute_spin(&spin_id2, &spin_hash2, &signature, &executor2);
+    let signature1 = sign_spin(&client, &spin_id1, &spin_hash1, &executor1);
+    let signature2 = sign_spin(&client, &spin_id2, &spin_hash2, &executor2);
+    client.execute_spin(
+        &spin_id1,
+        &spin_hash1,
+        &signature1,
+        &executor1,
+        &SPIN_EXPIRY,
+    );
+    client.execute_spin(
+        &spin_id2,
+        &spin_hash2,
+        &signature2,
+        &executor2,
+        &SPIN_EXPIRY,
+    );
 
     let execution1 = client.get_spin_execution(&spin_id1);
     let execution2 = client.get_spin_execution(&spin_id2);
@@ -420,20 +3089,22 @@ fn execute_spin_with_zero_ttl_immediate_cleanup() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let backend_signer = Address::generate(&env);
-    let executor = Address::generate(&env);
-    let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend_signer);
+    let (client, _, executor) = setup_test(&env);
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
+    let signature = sign_spin(&client, &spin_id, &spin_hash, &executor);
 
     // Execute with 0 TTL - operation is immediately expired per is_expired logic
     // (timestamp - executed_at >= 0 is always true when timestamp >= executed_at)
-    client.execute_spin_with_ttl(&spin_id, &spin_hash, &signature, &executor, &Some(0));
+    client.execute_spin_with_ttl(
+        &spin_id,
+        &spin_hash,
+        &signature,
+        &executor,
+        &SPIN_EXPIRY,
+        &Some(0),
+    );
 
     // With TTL of 0, the operation is considered expired immediately
     // so it won't be stored (it gets cleaned up during ensure_not_replayed)
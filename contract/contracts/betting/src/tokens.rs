@@ -0,0 +1,62 @@
+use common::ContractError;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::DataKey;
+
+/// Stake bounds for a token accepted for betting, in the token's smallest
+/// unit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenConfig {
+    pub token: Address,
+    pub min_stake: i128,
+    pub max_stake: i128,
+    pub decimals: u32,
+}
+
+pub fn get_config(env: &Env, token: &Address) -> Option<TokenConfig> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenConfig(token.clone()))
+}
+
+pub fn allowed_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AllowedTokens)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn allow(env: &Env, config: &TokenConfig) {
+    let storage = env.storage().persistent();
+    let mut tokens = allowed_tokens(env);
+    if !tokens.contains(&config.token) {
+        tokens.push_back(config.token.clone());
+        storage.set(&DataKey::AllowedTokens, &tokens);
+    }
+    storage.set(&DataKey::TokenConfig(config.token.clone()), config);
+}
+
+pub fn disallow(env: &Env, token: &Address) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    let mut tokens = allowed_tokens(env);
+    let index = tokens
+        .first_index_of(token)
+        .ok_or(ContractError::TokenNotAllowed)?;
+    tokens.remove(index);
+    storage.set(&DataKey::AllowedTokens, &tokens);
+    storage.remove(&DataKey::TokenConfig(token.clone()));
+    Ok(())
+}
+
+/// Check that a stake is in an allowlisted token and within its bounds.
+pub fn ensure_stake_allowed(env: &Env, token: &Address, amount: i128) -> Result<(), ContractError> {
+    let config = get_config(env, token).ok_or(ContractError::TokenNotAllowed)?;
+    if amount < config.min_stake {
+        return Err(ContractError::BelowMinStake);
+    }
+    if amount > config.max_stake {
+        return Err(ContractError::AboveMaxStake);
+    }
+    Ok(())
+}
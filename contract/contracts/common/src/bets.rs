@@ -0,0 +1,74 @@
+use soroban_sdk::{contracttype, Address, BytesN, Symbol, U256};
+
+use crate::enums::BetOutcome;
+
+// Bet records are stored by the betting contract and read back by the
+// settlement contract, so both sides share these definitions.
+
+/// Lifecycle of a single bet held in escrow.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BetState {
+    Active,
+    Cancelled,
+    Void,
+    Won,
+    Lost,
+    CashedOut,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bet {
+    pub bet_id: U256,
+    pub bettor: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub match_id: BytesN<32>,
+    pub bet_type: Symbol,
+    pub odds: u32, // Betting odds (multiplied by 100)
+    pub timestamp: u64,
+    pub state: BetState,
+    /// Placed with a backend-issued voucher, so the stake was never escrowed
+    /// or locked and is not paid back.
+    pub free_bet: bool,
+}
+
+impl Bet {
+    /// Part of the stake the bettor paid for and that is held for the bet
+    pub fn funded_stake(&self) -> i128 {
+        if self.free_bet {
+            0
+        } else {
+            self.amount
+        }
+    }
+}
+
+/// Odds are stored multiplied by 100 (2.50 -> 250)
+pub const ODDS_SCALE: i128 = 100;
+
+/// Payout of a winning stake at the given odds, rounded down.
+pub fn compute_payout(amount: i128, odds: u32) -> Option<i128> {
+    amount.checked_mul(odds as i128).map(|v| v / ODDS_SCALE)
+}
+
+/// Amount returned to the bettor for a bet settled with `outcome`. On half
+/// outcomes the odd unit of the stake goes to the half that is pushed. A
+/// free bet only pays its winnings, since its stake was never the bettor's.
+pub fn settlement_payout(bet: &Bet, outcome: BetOutcome) -> Option<i128> {
+    let amount = bet.amount;
+    let half = amount / 2;
+    let payout = match outcome {
+        BetOutcome::Win => compute_payout(amount, bet.odds)?,
+        BetOutcome::Lose => 0,
+        BetOutcome::Push => amount,
+        BetOutcome::HalfWin => compute_payout(half, bet.odds)?.checked_add(amount - half)?,
+        BetOutcome::HalfLose => amount - half,
+    };
+    if bet.free_bet {
+        Some((payout - amount).max(0))
+    } else {
+        Some(payout)
+    }
+}
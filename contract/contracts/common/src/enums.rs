@@ -1,4 +1,4 @@
-use soroban_sdk::contracterror;
+use soroban_sdk::{contracterror, contracttype};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -10,11 +10,15 @@ pub enum BetStatus {
     Cancelled = 3,
 }
 
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
+/// How a single bet settles. `Push` refunds the stake and covers both
+/// draws and voided bets; the half outcomes settle Asian-handicap quarter
+/// lines, where half of the stake is pushed and the other half wins or loses.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum BetOutcome {
-    Win = 0,
-    Lose = 1,
-    Draw = 2,
+    Win,
+    Lose,
+    Push,
+    HalfWin,
+    HalfLose,
 }
@@ -23,4 +23,30 @@ pub enum ContractError {
     AlreadyInitialized = 17,
     BetAlreadyPlaced = 18,
     DuplicateOperation = 19,
+    SignatureExpired = 20,
+    MatchNotFound = 21,
+    MatchAlreadyExists = 22,
+    BettingClosed = 23,
+    InvalidTimeWindow = 24,
+    PayoutMismatch = 25,
+    LiabilityExceeded = 26,
+    TokenNotAllowed = 27,
+    AboveMaxStake = 28,
+    ResultNotFinal = 29,
+    NotClaimable = 30,
+    BetCashedOut = 31,
+    PoolNotFound = 32,
+    PoolAlreadyExists = 33,
+    SelfExcluded = 34,
+    WagerLimitExceeded = 35,
+    LossLimitExceeded = 36,
+    Paused = 37,
+    InvalidGuardian = 38,
+    OddsStale = 39,
+    OddsMoved = 40,
+    ProposalNotFound = 41,
+    ProposalPending = 42,
+    DisputeWindowClosed = 43,
+    MatchFrozen = 44,
+    InvalidQuorum = 45,
 }
@@ -1,5 +1,7 @@
 use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Map, String, Symbol, U256};
 
+use crate::enums::BetOutcome;
+
 // ===== CORE EVENTS =====
 
 #[contracttype]
@@ -30,6 +32,7 @@ pub struct UnstakeEvent {
 pub struct BetEvent {
     pub bettor: Address,
     pub amount: i128,
+    pub token: Address,
     pub bet_id: U256,
     pub betting_contract: Address,
     pub timestamp: u64,
@@ -46,7 +49,7 @@ pub struct SettlementEvent {
     pub payout: i128,
     pub betting_contract: Address,
     pub timestamp: u64,
-    pub settlement_type: Symbol,
+    pub settlement_type: BetOutcome,
     pub final_odds: u32,
     pub metadata: Map<Symbol, String>,
 }
@@ -151,10 +154,12 @@ pub fn create_unstake_event(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_bet_event(
     env: &Env,
     bettor: Address,
     amount: i128,
+    token: Address,
     bet_id: U256,
     betting_contract: Address,
     bet_type: Symbol,
@@ -163,6 +168,7 @@ pub fn create_bet_event(
     BetEvent {
         bettor,
         amount,
+        token,
         bet_id,
         betting_contract,
         timestamp: 0, // Will be set by contract
@@ -178,7 +184,7 @@ pub fn create_settlement_event(
     winner: Address,
     payout: i128,
     betting_contract: Address,
-    settlement_type: Symbol,
+    settlement_type: BetOutcome,
     final_odds: u32,
 ) -> SettlementEvent {
     SettlementEvent {
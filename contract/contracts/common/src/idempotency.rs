@@ -72,7 +72,6 @@ fn is_expired(env: &Env, record: &ExecutionRecord) -> bool {
     }
 }
 
-#[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
 fn emit_replay_rejected(env: &Env, scope: Symbol, operation_hash: BytesN<32>) {
     let event = ReplayRejectedEvent {
         operation_hash,
@@ -80,6 +79,7 @@ fn emit_replay_rejected(env: &Env, scope: Symbol, operation_hash: BytesN<32>) {
         timestamp: env.ledger().timestamp(),
     };
 
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
     env.events()
         .publish((Symbol::new(env, REPLAY_REJECTED_TOPIC),), event);
 }
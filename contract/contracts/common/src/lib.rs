@@ -1,14 +1,20 @@
 #![no_std]
 
+pub mod bets;
 pub mod enums;
 pub mod errors;
 pub mod events;
 pub mod getters;
 pub mod idempotency;
+pub mod matches;
+pub mod pause;
 pub mod view_functions;
 
+pub use bets::*;
 pub use enums::*;
 pub use errors::*;
 pub use events::*;
 pub use getters::*;
 pub use idempotency::*;
+pub use matches::*;
+pub use pause::*;
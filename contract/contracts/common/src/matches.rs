@@ -0,0 +1,28 @@
+use soroban_sdk::{contracttype, BytesN};
+
+// Matches are stored by the betting contract and read back by the
+// settlement contract, so both sides share these definitions.
+
+/// Lifecycle of a match in the registry.
+///
+/// Scheduled -> Open -> Locked -> Settled, with Void reachable from any
+/// state before settlement.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MatchStatus {
+    Scheduled,
+    Open,
+    Locked,
+    Settled,
+    Void,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    pub match_id: BytesN<32>,
+    pub open_time: u64,
+    pub lock_time: u64,
+    pub status: MatchStatus,
+    pub created_at: u64,
+}
@@ -0,0 +1,68 @@
+use crate::ContractError;
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+/// Areas of a contract that the guardian can pause independently.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PauseScope {
+    Bets,
+    Spins,
+    Settlement,
+    Withdrawals,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Guardian,
+    Paused(PauseScope),
+}
+
+pub fn get_guardian(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Guardian)
+}
+
+/// Set the guardian allowed to pause and unpause the contract. Callers must
+/// make sure the guardian is not the backend signer.
+pub fn set_guardian(env: &Env, guardian: &Address) {
+    env.storage().persistent().set(&DataKey::Guardian, guardian);
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events()
+        .publish((Symbol::new(env, "guardian_set"),), guardian.clone());
+}
+
+pub fn require_guardian(env: &Env, guardian: &Address) -> Result<(), ContractError> {
+    let stored = get_guardian(env).ok_or(ContractError::Unauthorized)?;
+    guardian.require_auth();
+    if *guardian != stored {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+pub fn is_paused(env: &Env, scope: PauseScope) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Paused(scope))
+        .unwrap_or(false)
+}
+
+pub fn set_paused(env: &Env, scope: PauseScope, paused: bool) {
+    let key = DataKey::Paused(scope);
+    if paused {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    let topic = if paused { "paused" } else { "unpaused" };
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events()
+        .publish((Symbol::new(env, topic), scope), env.ledger().timestamp());
+}
+
+pub fn ensure_not_paused(env: &Env, scope: PauseScope) -> Result<(), ContractError> {
+    if is_paused(env, scope) {
+        return Err(ContractError::Paused);
+    }
+    Ok(())
+}
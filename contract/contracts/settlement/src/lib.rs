@@ -1,26 +1,87 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, U256};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal, Map,
+    Symbol, Val, Vec, U256,
+};
 
-use common::ContractError;
+use common::{
+    ensure_not_paused, settlement_payout, Bet, BetOutcome, BetState, ContractError, Match,
+    MatchStatus, PauseScope,
+};
 
+mod oracles;
+
+pub use oracles::{MatchResult, ResultStatus};
+
+/// Settlement awaiting its dispute window. Funds only move once the window
+/// has passed and the proposal is finalized.
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementProposal {
+    pub bet_id: U256,
+    pub outcome: BetOutcome,
+    pub payout: i128,
+    pub proposed_at: u64,
+    pub finalizes_at: u64,
+}
+
+/// Outcome and payout a settlement had before it was corrected
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementRevision {
+    pub outcome: BetOutcome,
+    pub payout: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SettlementRecord {
     pub bet_id: U256,
-    pub outcome: Symbol,
+    pub outcome: BetOutcome,
     pub bettor: Address,
     pub winner: Option<Address>,
     pub payout: i128,
     pub timestamp: u64,
+    /// Earlier versions of this settlement, oldest first
+    pub revisions: Vec<SettlementRevision>,
+}
+
+/// Upper bound on bets settled per `settle_match` call
+pub const MATCH_SETTLEMENT_PAGE: u32 = 25;
+
+/// Progress of settling every bet of a match against its result. Pages
+/// are only processed from `finalizes_at`, once the dispute window is over.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchSettlement {
+    pub match_id: BytesN<32>,
+    pub outcome: Symbol,
+    pub finalizes_at: u64,
+    pub next_index: u32,
+    pub settled_bets: u32,
+    pub completed: bool,
 }
 
 #[contracttype]
 #[derive(Clone)]
 enum DataKey {
+    Admin,
     BackendSigner,
     BalanceLedgerContract,
+    BettingContract,
     Settled(U256),
+    MatchSettlement(BytesN<32>),
+    Proposal(U256),
+    DisputeWindow,
+    Disputer,
+    Reporters,
+    Quorum,
+    Reports(BytesN<32>),
+    MatchResult(BytesN<32>),
+    Dissents(Address),
+    HalfOutcome(U256),
 }
 
 #[contract]
@@ -28,10 +89,33 @@ pub struct SettlementContract;
 
 #[contractimpl]
 impl SettlementContract {
-    pub fn initialize(env: Env, backend_signer: Address, balance_ledger: Address) {
+    /// Wire the contract up at deployment. The admin owns the guardian and
+    /// is kept apart from the backend signer that settles bets.
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        backend: Address,
+        ledger: Address,
+        betting: Address,
+    ) {
         let storage = env.storage().persistent();
-        storage.set(&DataKey::BackendSigner, &backend_signer);
-        storage.set(&DataKey::BalanceLedgerContract, &balance_ledger);
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::BackendSigner, &backend);
+        storage.set(&DataKey::BalanceLedgerContract, &ledger);
+        storage.set(&DataKey::BettingContract, &betting);
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+        let stored: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)?;
+        if stored != *admin {
+            return Err(ContractError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
     }
 
     fn require_backend_auth(env: &Env) -> Result<(), ContractError> {
@@ -43,86 +127,798 @@ impl SettlementContract {
         Ok(())
     }
 
+    /// Set the guardian allowed to pause settlement. The guardian must not be
+    /// the admin or the backend signer, so a leaked backend key cannot lift
+    /// a pause.
+    pub fn set_guardian(env: Env, admin: Address, guardian: Address) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        let backend: Option<Address> = env.storage().persistent().get(&DataKey::BackendSigner);
+        if guardian == admin || Some(guardian.clone()) == backend {
+            return Err(ContractError::InvalidGuardian);
+        }
+        common::set_guardian(&env, &guardian);
+        Ok(())
+    }
+
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        common::get_guardian(&env)
+    }
+
+    pub fn pause(env: Env, guardian: Address, scope: PauseScope) -> Result<(), ContractError> {
+        common::require_guardian(&env, &guardian)?;
+        common::set_paused(&env, scope, true);
+        Ok(())
+    }
+
+    pub fn unpause(env: Env, guardian: Address, scope: PauseScope) -> Result<(), ContractError> {
+        common::require_guardian(&env, &guardian)?;
+        common::set_paused(&env, scope, false);
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        common::is_paused(&env, scope)
+    }
+
+    /// Set how long a proposed settlement can be rejected before it can be
+    /// finalized. Zero finalizes settlements as soon as they are proposed.
+    pub fn set_dispute_window(env: Env, admin: Address, seconds: u64) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::DisputeWindow, &seconds);
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "dispute_window_set"),), seconds);
+        Ok(())
+    }
+
+    pub fn get_dispute_window(env: Env) -> u64 {
+        dispute_window(&env)
+    }
+
+    /// Set the address allowed, besides the backend signer and the admin, to
+    /// reject proposed settlements during their dispute window.
+    pub fn set_disputer(env: Env, admin: Address, disputer: Address) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Disputer, &disputer);
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "disputer_set"),), disputer);
+        Ok(())
+    }
+
+    pub fn get_disputer(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Disputer)
+    }
+
+    pub fn get_proposal(env: Env, bet_id: U256) -> Option<SettlementProposal> {
+        env.storage().persistent().get(&DataKey::Proposal(bet_id))
+    }
+
+    /// Register a result reporter. Once any reporter is registered, match
+    /// outcomes can only be settled after reporters reach the quorum.
+    pub fn add_reporter(env: Env, admin: Address, reporter: Address) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        oracles::add_reporter(&env, &reporter)?;
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "reporter_added"),), reporter);
+        Ok(())
+    }
+
+    pub fn remove_reporter(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        oracles::remove_reporter(&env, &reporter)?;
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events()
+            .publish((Symbol::new(&env, "reporter_removed"),), reporter);
+        Ok(())
+    }
+
+    pub fn get_reporters(env: Env) -> Vec<Address> {
+        oracles::reporters(&env)
+    }
+
+    /// Set how many reporters must agree on a result before it is accepted
+    pub fn set_quorum(env: Env, admin: Address, quorum: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        oracles::set_quorum(&env, quorum)
+    }
+
+    pub fn get_quorum(env: Env) -> u32 {
+        oracles::quorum(&env)
+    }
+
+    /// Submit a registered reporter's result for a match. Each reporter
+    /// reports once per match, authorizing the report with its own key.
+    pub fn submit_result(
+        env: Env,
+        reporter: Address,
+        match_id: BytesN<32>,
+        outcome: Symbol,
+    ) -> Result<MatchResult, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
+        reporter.require_auth();
+
+        let result = oracles::submit(&env, &reporter, &match_id, &outcome)?;
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "result_reported"), match_id),
+            (reporter, outcome),
+        );
+        Ok(result)
+    }
+
+    /// Sign off a half outcome for a bet on an Asian-handicap quarter line.
+    /// Reported results carry no handicap line, so with reporters registered
+    /// a bet can only settle or be corrected to a half outcome the admin has
+    /// approved.
+    pub fn approve_half_outcome(
+        env: Env,
+        admin: Address,
+        bet_id: U256,
+        outcome: BetOutcome,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        if !matches!(outcome, BetOutcome::HalfWin | BetOutcome::HalfLose) {
+            return Err(ContractError::InvalidStatus);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::HalfOutcome(bet_id.clone()), &outcome);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "half_outcome_approved"), bet_id),
+            (admin, outcome),
+        );
+        Ok(())
+    }
+
+    /// Get the half outcome the admin approved for a bet, if any
+    pub fn get_half_outcome(env: Env, bet_id: U256) -> Option<BetOutcome> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HalfOutcome(bet_id))
+    }
+
+    /// Decide the result of a match frozen by conflicting reports, after
+    /// manual review. Caller must be the admin.
+    pub fn resolve_frozen_match(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+        outcome: Symbol,
+    ) -> Result<MatchResult, ContractError> {
+        Self::require_admin(&env, &admin)?;
+        oracles::resolve(&env, &match_id, &outcome)
+    }
+
+    pub fn get_match_result(env: Env, match_id: BytesN<32>) -> MatchResult {
+        oracles::get_result(&env, &match_id)
+    }
+
+    pub fn get_match_reports(env: Env, match_id: BytesN<32>) -> Map<Address, Symbol> {
+        oracles::get_reports(&env, &match_id)
+    }
+
+    /// Number of accepted results a reporter disagreed with
+    pub fn get_dissent_count(env: Env, reporter: Address) -> u32 {
+        oracles::dissent_count(&env, &reporter)
+    }
+
     pub fn is_settled(env: Env, bet_id: U256) -> bool {
         env.storage().persistent().has(&DataKey::Settled(bet_id))
     }
 
-    /// Settle a bet. Caller must be backend signer (oracle/admin).
-    /// Supports WIN, LOSS, DRAW (refund).
+    /// Compute the payout of a bet for an outcome from the stake and odds
+    /// stored by the betting contract.
+    pub fn compute_payout(
+        env: Env,
+        bet_id: U256,
+        outcome: BetOutcome,
+    ) -> Result<i128, ContractError> {
+        let bet = Self::load_bet(&env, &bet_id)?;
+        payout_for(&bet, outcome)
+    }
+
+    /// Propose the settlement of a bet. Caller must be backend signer
+    /// (oracle/admin). `Push` refunds the stake; the half outcomes settle
+    /// Asian-handicap quarter lines.
+    ///
+    /// The stake and odds are read from the betting contract and the payout
+    /// is computed on-chain; `payout` is the caller's expected value and the
+    /// call is rejected if it differs. The proposal can be rejected until the
+    /// dispute window ends and is then finalized by `finalize_settlement`;
+    /// with no dispute window it is finalized right away.
     pub fn settle_bet(
         env: Env,
         bet_id: U256,
-        bettor: Address,
-        winner: Option<Address>,
-        bet_amount: i128,
         payout: i128,
-        settlement_type: Symbol,
-    ) -> Result<(), ContractError> {
+        outcome: BetOutcome,
+    ) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
         Self::require_backend_auth(&env)?;
 
         let storage = env.storage().persistent();
         if storage.has(&DataKey::Settled(bet_id.clone())) {
             return Err(ContractError::BetAlreadySettled);
         }
+        if storage.has(&DataKey::Proposal(bet_id.clone())) {
+            return Err(ContractError::ProposalPending);
+        }
+
+        let bet = Self::load_bet(&env, &bet_id)?;
+        ensure_settleable(&bet)?;
+
+        ensure_agrees_with_result(&env, &bet, outcome)?;
+
+        let computed = payout_for(&bet, outcome)?;
+        if payout != computed {
+            return Err(ContractError::PayoutMismatch);
+        }
+
+        let now = env.ledger().timestamp();
+        let proposal = SettlementProposal {
+            bet_id: bet_id.clone(),
+            outcome,
+            payout: computed,
+            proposed_at: now,
+            finalizes_at: dispute_ends_at(&env, now)?,
+        };
+        storage.set(&DataKey::Proposal(bet_id.clone()), &proposal);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "settlement_proposed"), bet_id),
+            (proposal.outcome, proposal.payout, proposal.finalizes_at),
+        );
+
+        if proposal.finalizes_at <= now {
+            Self::finalize(&env, &proposal, bet)?;
+        }
+        Ok(computed)
+    }
+
+    /// Apply a proposed settlement once its dispute window has passed.
+    /// Caller must be backend signer, as the balance ledger only accepts
+    /// fund moves it has authorized.
+    pub fn finalize_settlement(env: Env, bet_id: U256) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
+        Self::require_backend_auth(&env)?;
+
+        let proposal: SettlementProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(bet_id.clone()))
+            .ok_or(ContractError::ProposalNotFound)?;
+        if env.ledger().timestamp() < proposal.finalizes_at {
+            return Err(ContractError::ResultNotFinal);
+        }
+
+        let bet = Self::load_bet(&env, &bet_id)?;
+        ensure_settleable(&bet)?;
+        Self::finalize(&env, &proposal, bet)?;
+        Ok(proposal.payout)
+    }
+
+    /// Reject a proposed settlement during its dispute window, so the bet
+    /// can be proposed again with the corrected result. Caller must be the
+    /// backend signer, the admin or the disputer.
+    pub fn reject_settlement(env: Env, caller: Address, bet_id: U256) -> Result<(), ContractError> {
+        Self::require_dispute_auth(&env, &caller)?;
+
+        let storage = env.storage().persistent();
+        let key = DataKey::Proposal(bet_id.clone());
+        let proposal: SettlementProposal =
+            storage.get(&key).ok_or(ContractError::ProposalNotFound)?;
+        if env.ledger().timestamp() >= proposal.finalizes_at {
+            return Err(ContractError::DisputeWindowClosed);
+        }
+        storage.remove(&key);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "settlement_rejected"), bet_id),
+            (caller, proposal.outcome),
+        );
+        Ok(())
+    }
 
-        // Get balance ledger contract address
+    fn finalize(env: &Env, proposal: &SettlementProposal, bet: Bet) -> Result<(), ContractError> {
+        let storage = env.storage().persistent();
         let bal_contract: Address = storage
             .get(&DataKey::BalanceLedgerContract)
             .ok_or(ContractError::Unauthorized)?;
 
-        let win_sym = Symbol::short("WIN");
-        let loss_sym = Symbol::short("LOSS");
-        let draw_sym = Symbol::short("DRAW");
+        let winner = winner_of(&bet, proposal.outcome);
 
-        // Perform atomic fund updates by invoking balance ledger contract methods.
-        if settlement_type == win_sym {
-            // Winner must be provided
-            let winner_addr = winner.ok_or(ContractError::InvalidBet)?;
+        // Perform atomic fund updates by invoking balance ledger contract methods:
+        // the locked stake is always released and the payout (full or half
+        // winnings, the refunded part of the stake, or nothing on a loss) is
+        // credited as withdrawable. A free bet never locked a stake.
+        apply_delta(
+            env,
+            &bal_contract,
+            &bet.bettor,
+            proposal.payout,
+            -bet.funded_stake(),
+        );
 
-            // Deduct locked funds from bettor
-            env.invoke_contract(
-                &bal_contract,
-                &Symbol::new(&env, "apply_delta"),
-                (bettor.clone(), 0i128, -bet_amount),
-            );
+        // Mark settled and store record
+        let record = SettlementRecord {
+            bet_id: proposal.bet_id.clone(),
+            outcome: proposal.outcome,
+            bettor: bet.bettor,
+            winner,
+            payout: proposal.payout,
+            timestamp: env.ledger().timestamp(),
+            revisions: Vec::new(env),
+        };
+        storage.set(&DataKey::Settled(proposal.bet_id.clone()), &record);
+        storage.remove(&DataKey::Proposal(proposal.bet_id.clone()));
+        mark_settled(
+            env,
+            vec![
+                env,
+                (
+                    proposal.bet_id.clone(),
+                    settled_state(proposal.outcome),
+                    proposal.payout,
+                ),
+            ],
+        )?;
 
-            // Credit payout to winner withdrawable
-            env.invoke_contract(
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (
+                Symbol::new(env, "settlement_finalized"),
+                proposal.bet_id.clone(),
+            ),
+            (proposal.outcome, proposal.payout),
+        );
+        Ok(())
+    }
+
+    /// Correct a finalized settlement to a new outcome. Corrections are
+    /// authorized by the admin; the backend signer also signs, as it does
+    /// every balance ledger update. The difference between the new and the
+    /// recorded payout is credited to or clawed back from the bettor's
+    /// withdrawable balance; a clawback the balance cannot cover is tracked
+    /// by the balance ledger as receivable. The replaced outcome is kept in
+    /// the record's revision history. A correction need not match the result
+    /// accepted by reporters, so a wrongly accepted result can be undone.
+    pub fn resettle_bet(
+        env: Env,
+        admin: Address,
+        bet_id: U256,
+        payout: i128,
+        outcome: BetOutcome,
+    ) -> Result<i128, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
+        Self::require_admin(&env, &admin)?;
+        Self::require_backend_auth(&env)?;
+
+        let storage = env.storage().persistent();
+        let mut record: SettlementRecord = storage
+            .get(&DataKey::Settled(bet_id.clone()))
+            .ok_or(ContractError::InvalidStatus)?;
+        if record.outcome == outcome {
+            return Err(ContractError::DuplicateOperation);
+        }
+
+        let bet = Self::load_bet(&env, &bet_id)?;
+        // The admin may correct away from a wrongly accepted result, but a
+        // half outcome still needs its own approval
+        if matches!(outcome, BetOutcome::HalfWin | BetOutcome::HalfLose) {
+            ensure_agrees_with_result(&env, &bet, outcome)?;
+        }
+        let computed = payout_for(&bet, outcome)?;
+        if payout != computed {
+            return Err(ContractError::PayoutMismatch);
+        }
+
+        let correction = computed - record.payout;
+        if correction != 0 {
+            let bal_contract: Address = storage
+                .get(&DataKey::BalanceLedgerContract)
+                .ok_or(ContractError::Unauthorized)?;
+            let _: i128 = env.invoke_contract(
                 &bal_contract,
-                &Symbol::new(&env, "apply_delta"),
-                (winner_addr.clone(), payout, 0i128),
+                &Symbol::new(&env, "apply_correction"),
+                vec![&env, bet.bettor.into_val(&env), correction.into_val(&env)],
             );
-        } else if settlement_type == loss_sym {
-            // Remove locked funds from bettor (platform keeps funds)
-            env.invoke_contract(
-                &bal_contract,
-                &Symbol::new(&env, "apply_delta"),
-                (bettor.clone(), 0i128, -bet_amount),
+        }
+
+        let previous = record.outcome;
+        record.revisions.push_back(SettlementRevision {
+            outcome: record.outcome,
+            payout: record.payout,
+            timestamp: record.timestamp,
+        });
+        record.outcome = outcome;
+        record.winner = winner_of(&bet, outcome);
+        record.payout = computed;
+        record.timestamp = env.ledger().timestamp();
+        storage.set(&DataKey::Settled(bet_id.clone()), &record);
+        mark_settled(
+            &env,
+            vec![&env, (bet_id.clone(), settled_state(outcome), computed)],
+        )?;
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "settlement_corrected"), bet_id),
+            (previous, outcome, correction, record.revisions.len()),
+        );
+        Ok(correction)
+    }
+
+    pub fn get_settlement(env: Env, bet_id: U256) -> Option<SettlementRecord> {
+        env.storage().persistent().get(&DataKey::Settled(bet_id))
+    }
+
+    /// Whether a bet is settled, proposed for settlement, or on a match
+    /// whose settlement has been proposed. The betting contract checks this
+    /// before paying a bet out of escrow.
+    pub fn has_settlement(env: Env, bet_id: U256, match_id: BytesN<32>) -> bool {
+        let storage = env.storage().persistent();
+        storage.has(&DataKey::Settled(bet_id.clone()))
+            || storage.has(&DataKey::Proposal(bet_id))
+            || storage.has(&DataKey::MatchSettlement(match_id))
+    }
+
+    fn require_dispute_auth(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        let storage = env.storage().persistent();
+        let backend: Address = storage
+            .get(&DataKey::BackendSigner)
+            .ok_or(ContractError::Unauthorized)?;
+        let admin: Option<Address> = storage.get(&DataKey::Admin);
+        let disputer: Option<Address> = storage.get(&DataKey::Disputer);
+        caller.require_auth();
+        if *caller != backend && Some(caller.clone()) != admin && Some(caller.clone()) != disputer {
+            return Err(ContractError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Settle the next page of bets on a match against its result. Bets on
+    /// the `outcome` selection win and all others lose; an outcome of `VOID`
+    /// refunds every bet. Bets already settled or no longer active are
+    /// skipped. The balance changes of a page go to the ledger in one call,
+    /// and the stored cursor lets the next call resume where this one ended.
+    ///
+    /// The first call proposes the outcome; pages are settled once the
+    /// dispute window has passed. The match must be locked in the betting
+    /// contract when the outcome is proposed. With result reporters
+    /// registered, the outcome must be the one they accepted.
+    pub fn settle_match(
+        env: Env,
+        match_id: BytesN<32>,
+        outcome: Symbol,
+    ) -> Result<MatchSettlement, ContractError> {
+        ensure_not_paused(&env, PauseScope::Settlement)?;
+        Self::require_backend_auth(&env)?;
+
+        let storage = env.storage().persistent();
+        let progress_key = DataKey::MatchSettlement(match_id.clone());
+        let now = env.ledger().timestamp();
+        let mut progress: MatchSettlement = match storage.get(&progress_key) {
+            Some(progress) => progress,
+            None => {
+                // Bets are only settled once betting on the match has closed
+                if Self::load_match(&env, &match_id)?.status != MatchStatus::Locked {
+                    return Err(ContractError::InvalidStatus);
+                }
+                if let Some(accepted) = oracles::accepted_outcome(&env, &match_id)? {
+                    if accepted != outcome {
+                        return Err(ContractError::InvalidStatus);
+                    }
+                }
+                let progress = MatchSettlement {
+                    match_id: match_id.clone(),
+                    outcome: outcome.clone(),
+                    finalizes_at: dispute_ends_at(&env, now)?,
+                    next_index: 0,
+                    settled_bets: 0,
+                    completed: false,
+                };
+                storage.set(&progress_key, &progress);
+                #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+                env.events().publish(
+                    (
+                        Symbol::new(&env, "match_settlement_proposed"),
+                        match_id.clone(),
+                    ),
+                    (outcome.clone(), progress.finalizes_at),
+                );
+                progress
+            }
+        };
+        if progress.outcome != outcome {
+            return Err(ContractError::InvalidStatus);
+        }
+        if progress.completed || now < progress.finalizes_at {
+            return Ok(progress);
+        }
+
+        let bal_contract: Address = storage
+            .get(&DataKey::BalanceLedgerContract)
+            .ok_or(ContractError::Unauthorized)?;
+        let betting: Address = storage
+            .get(&DataKey::BettingContract)
+            .ok_or(ContractError::NotInitialized)?;
+
+        let total: u32 = env.invoke_contract(
+            &betting,
+            &Symbol::new(&env, "get_match_bet_count"),
+            vec![&env, match_id.into_val(&env)],
+        );
+        let bet_ids: Vec<U256> = env.invoke_contract(
+            &betting,
+            &Symbol::new(&env, "get_match_bets"),
+            vec![
+                &env,
+                match_id.into_val(&env),
+                progress.next_index.into_val(&env),
+                MATCH_SETTLEMENT_PAGE.into_val(&env),
+            ],
+        );
+
+        // Net balance change per bettor across the page
+        let mut deltas: Map<Address, (i128, i128)> = Map::new(&env);
+        let mut results: Vec<(U256, BetState, i128)> = Vec::new(&env);
+        let mut page_settled = 0u32;
+        for bet_id in bet_ids.iter() {
+            // Bets settled or proposed one by one are left to that flow
+            if storage.has(&DataKey::Settled(bet_id.clone()))
+                || storage.has(&DataKey::Proposal(bet_id.clone()))
+            {
+                continue;
+            }
+            let bet = Self::load_bet(&env, &bet_id)?;
+            if bet.state != BetState::Active {
+                continue;
+            }
+
+            let bet_outcome = outcome_for(&bet, &outcome);
+            let payout = payout_for(&bet, bet_outcome)?;
+
+            let (withdrawable, locked) = deltas.get(bet.bettor.clone()).unwrap_or((0, 0));
+            deltas.set(
+                bet.bettor.clone(),
+                (withdrawable + payout, locked - bet.funded_stake()),
             );
-        } else if settlement_type == draw_sym {
-            // Refund: move locked funds back to withdrawable
-            env.invoke_contract(
+
+            let winner = winner_of(&bet, bet_outcome);
+            let record = SettlementRecord {
+                bet_id: bet_id.clone(),
+                outcome: bet_outcome,
+                bettor: bet.bettor,
+                winner,
+                payout,
+                timestamp: env.ledger().timestamp(),
+                revisions: Vec::new(&env),
+            };
+            storage.set(&DataKey::Settled(bet_id.clone()), &record);
+            results.push_back((bet_id, settled_state(bet_outcome), payout));
+            page_settled += 1;
+        }
+
+        if !deltas.is_empty() {
+            let mut entries: Vec<(Address, i128, i128)> = Vec::new(&env);
+            for (bettor, (withdrawable, locked)) in deltas.iter() {
+                entries.push_back((bettor, withdrawable, locked));
+            }
+            let _: Val = env.invoke_contract(
                 &bal_contract,
-                &Symbol::new(&env, "apply_delta"),
-                (bettor.clone(), bet_amount, -bet_amount),
+                &Symbol::new(&env, "apply_deltas"),
+                vec![&env, entries.into_val(&env)],
             );
-        } else {
-            return Err(ContractError::InvalidStatus);
+        }
+        if !results.is_empty() {
+            mark_settled(&env, results)?;
         }
 
-        // Mark settled and store record
-        let record = SettlementRecord {
-            bet_id: bet_id.clone(),
-            outcome: settlement_type.clone(),
-            bettor: bettor.clone(),
-            winner: winner.clone(),
-            payout,
-            timestamp: env.ledger().timestamp(),
-        };
-        storage.set(&DataKey::Settled(bet_id), &record);
+        progress.next_index += bet_ids.len();
+        progress.settled_bets += page_settled;
+        progress.completed = progress.next_index >= total;
+        storage.set(&progress_key, &progress);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "match_settlement"), match_id),
+            (progress.next_index, page_settled, progress.completed),
+        );
 
+        Ok(progress)
+    }
+
+    /// Reject a proposed match outcome during its dispute window. Caller
+    /// must be the backend signer, the admin or the disputer.
+    pub fn reject_match_settlement(
+        env: Env,
+        caller: Address,
+        match_id: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        Self::require_dispute_auth(&env, &caller)?;
+
+        let storage = env.storage().persistent();
+        let key = DataKey::MatchSettlement(match_id.clone());
+        let progress: MatchSettlement = storage.get(&key).ok_or(ContractError::ProposalNotFound)?;
+        if env.ledger().timestamp() >= progress.finalizes_at {
+            return Err(ContractError::DisputeWindowClosed);
+        }
+        storage.remove(&key);
+
+        #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+        env.events().publish(
+            (Symbol::new(&env, "match_settlement_rejected"), match_id),
+            (caller, progress.outcome),
+        );
         Ok(())
     }
+
+    pub fn get_match_settlement(env: Env, match_id: BytesN<32>) -> Option<MatchSettlement> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchSettlement(match_id))
+    }
+
+    fn load_bet(env: &Env, bet_id: &U256) -> Result<Bet, ContractError> {
+        let betting: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BettingContract)
+            .ok_or(ContractError::NotInitialized)?;
+
+        Ok(env.invoke_contract(
+            &betting,
+            &Symbol::new(env, "get_bet"),
+            vec![env, bet_id.into_val(env)],
+        ))
+    }
+
+    fn load_match(env: &Env, match_id: &BytesN<32>) -> Result<Match, ContractError> {
+        let betting: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BettingContract)
+            .ok_or(ContractError::NotInitialized)?;
+
+        Ok(env.invoke_contract(
+            &betting,
+            &Symbol::new(env, "get_match"),
+            vec![env, match_id.into_val(env)],
+        ))
+    }
+}
+
+fn dispute_window(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DisputeWindow)
+        .unwrap_or(0)
+}
+
+fn dispute_ends_at(env: &Env, now: u64) -> Result<u64, ContractError> {
+    now.checked_add(dispute_window(env))
+        .ok_or(ContractError::InvalidTimeWindow)
+}
+
+fn ensure_settleable(bet: &Bet) -> Result<(), ContractError> {
+    if bet.state == BetState::CashedOut {
+        return Err(ContractError::BetCashedOut);
+    }
+    if bet.state != BetState::Active {
+        return Err(ContractError::InvalidStatus);
+    }
+    Ok(())
+}
+
+/// With result reporters registered, a bet can only settle on the outcome
+/// of the accepted result. Reported results carry no handicap line, so a
+/// half outcome also needs the admin's approval for the bet.
+fn ensure_agrees_with_result(
+    env: &Env,
+    bet: &Bet,
+    outcome: BetOutcome,
+) -> Result<(), ContractError> {
+    let Some(accepted) = oracles::accepted_outcome(env, &bet.match_id)? else {
+        return Ok(());
+    };
+    let agrees = match outcome {
+        BetOutcome::HalfWin | BetOutcome::HalfLose => {
+            let approved: Option<BetOutcome> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HalfOutcome(bet.bet_id.clone()));
+            approved == Some(outcome)
+        }
+        _ => outcome == outcome_for(bet, &accepted),
+    };
+    if !agrees {
+        return Err(ContractError::InvalidStatus);
+    }
+    Ok(())
+}
+
+/// Outcome of a bet on a match decided on `result`: bets on the result
+/// win, a `VOID` result pushes every bet, and all others lose.
+fn outcome_for(bet: &Bet, result: &Symbol) -> BetOutcome {
+    if *result == symbol_short!("VOID") {
+        BetOutcome::Push
+    } else if bet.bet_type == *result {
+        BetOutcome::Win
+    } else {
+        BetOutcome::Lose
+    }
+}
+
+fn payout_for(bet: &Bet, outcome: BetOutcome) -> Result<i128, ContractError> {
+    settlement_payout(bet, outcome).ok_or(ContractError::InvalidAmount)
+}
+
+/// State the betting contract records for a bet settled with `outcome`
+fn settled_state(outcome: BetOutcome) -> BetState {
+    match outcome {
+        BetOutcome::Win | BetOutcome::HalfWin => BetState::Won,
+        BetOutcome::Lose | BetOutcome::HalfLose => BetState::Lost,
+        BetOutcome::Push => BetState::Void,
+    }
+}
+
+/// Close settled bets in the betting contract so they cannot also be paid
+/// out of its escrow.
+fn mark_settled(env: &Env, results: Vec<(U256, BetState, i128)>) -> Result<(), ContractError> {
+    let betting: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::BettingContract)
+        .ok_or(ContractError::NotInitialized)?;
+    let _: Val = env.invoke_contract(
+        &betting,
+        &Symbol::new(env, "mark_settled"),
+        vec![
+            env,
+            env.current_contract_address().into_val(env),
+            results.into_val(env),
+        ],
+    );
+    Ok(())
+}
+
+fn winner_of(bet: &Bet, outcome: BetOutcome) -> Option<Address> {
+    match outcome {
+        BetOutcome::Win | BetOutcome::HalfWin => Some(bet.bettor.clone()),
+        _ => None,
+    }
+}
+
+fn apply_delta(
+    env: &Env,
+    bal_contract: &Address,
+    user: &Address,
+    withdrawable_delta: i128,
+    locked_delta: i128,
+) {
+    let _: Val = env.invoke_contract(
+        bal_contract,
+        &Symbol::new(env, "apply_delta"),
+        vec![
+            env,
+            user.into_val(env),
+            withdrawable_delta.into_val(env),
+            locked_delta.into_val(env),
+        ],
+    );
 }
 
 #[cfg(test)]
@@ -0,0 +1,243 @@
+use common::ContractError;
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map, Symbol, Vec};
+
+use crate::DataKey;
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ResultStatus {
+    Pending,
+    Accepted,
+    Frozen,
+}
+
+/// Reporter consensus on the result of a match. A result is accepted once
+/// `quorum` reporters agree on it; the match is frozen for manual review as
+/// soon as no outcome can reach the quorum any more.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchResult {
+    pub match_id: BytesN<32>,
+    pub status: ResultStatus,
+    pub outcome: Option<Symbol>,
+    pub decided_at: u64,
+}
+
+pub fn reporters(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Reporters)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn quorum(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Quorum)
+        .unwrap_or(0)
+}
+
+pub fn add_reporter(env: &Env, reporter: &Address) -> Result<(), ContractError> {
+    let mut registered = reporters(env);
+    if registered.contains(reporter) {
+        return Err(ContractError::DuplicateOperation);
+    }
+    registered.push_back(reporter.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Reporters, &registered);
+    Ok(())
+}
+
+/// Deregister a reporter. The quorum has to be lowered first if it would
+/// exceed the number of remaining reporters.
+pub fn remove_reporter(env: &Env, reporter: &Address) -> Result<(), ContractError> {
+    let mut registered = reporters(env);
+    let index = registered
+        .first_index_of(reporter)
+        .ok_or(ContractError::Unauthorized)?;
+    if quorum(env) > registered.len() - 1 {
+        return Err(ContractError::InvalidQuorum);
+    }
+    registered.remove(index);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Reporters, &registered);
+    Ok(())
+}
+
+pub fn set_quorum(env: &Env, quorum: u32) -> Result<(), ContractError> {
+    if quorum == 0 || quorum > reporters(env).len() {
+        return Err(ContractError::InvalidQuorum);
+    }
+    env.storage().persistent().set(&DataKey::Quorum, &quorum);
+    Ok(())
+}
+
+pub fn get_result(env: &Env, match_id: &BytesN<32>) -> MatchResult {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MatchResult(match_id.clone()))
+        .unwrap_or(MatchResult {
+            match_id: match_id.clone(),
+            status: ResultStatus::Pending,
+            outcome: None,
+            decided_at: 0,
+        })
+}
+
+pub fn get_reports(env: &Env, match_id: &BytesN<32>) -> Map<Address, Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Reports(match_id.clone()))
+        .unwrap_or_else(|| Map::new(env))
+}
+
+pub fn dissent_count(env: &Env, reporter: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Dissents(reporter.clone()))
+        .unwrap_or(0)
+}
+
+/// Record a reporter's result for a match and accept or freeze the match
+/// once the reports allow it. Reports arriving after acceptance are still
+/// checked against the accepted outcome.
+pub fn submit(
+    env: &Env,
+    reporter: &Address,
+    match_id: &BytesN<32>,
+    outcome: &Symbol,
+) -> Result<MatchResult, ContractError> {
+    let registered = reporters(env);
+    if !registered.contains(reporter) {
+        return Err(ContractError::Unauthorized);
+    }
+    let quorum = quorum(env);
+    if quorum == 0 {
+        return Err(ContractError::InvalidQuorum);
+    }
+
+    let result = get_result(env, match_id);
+    if result.status == ResultStatus::Frozen {
+        return Err(ContractError::MatchFrozen);
+    }
+    let mut reports = get_reports(env, match_id);
+    if reports.contains_key(reporter.clone()) {
+        return Err(ContractError::DuplicateOperation);
+    }
+    reports.set(reporter.clone(), outcome.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Reports(match_id.clone()), &reports);
+
+    if let Some(accepted) = &result.outcome {
+        if outcome != accepted {
+            record_dissent(env, reporter, match_id, outcome, accepted);
+        }
+        return Ok(result);
+    }
+
+    let mut tallies: Map<Symbol, u32> = Map::new(env);
+    for (_, reported) in reports.iter() {
+        let count = tallies.get(reported.clone()).unwrap_or(0);
+        tallies.set(reported, count + 1);
+    }
+    if tallies.get(outcome.clone()).unwrap_or(0) >= quorum {
+        return Ok(accept(env, match_id, outcome));
+    }
+
+    let outstanding = registered.len().saturating_sub(reports.len());
+    let leading = tallies.values().iter().max().unwrap_or(0);
+    if leading + outstanding < quorum {
+        return Ok(freeze(env, match_id));
+    }
+    Ok(result)
+}
+
+/// Decide a frozen match after manual review.
+pub fn resolve(
+    env: &Env,
+    match_id: &BytesN<32>,
+    outcome: &Symbol,
+) -> Result<MatchResult, ContractError> {
+    if get_result(env, match_id).status != ResultStatus::Frozen {
+        return Err(ContractError::InvalidStatus);
+    }
+    Ok(accept(env, match_id, outcome))
+}
+
+/// Outcome reporters accepted for a match, or `None` when no reporters are
+/// registered and the backend decides results alone.
+pub fn accepted_outcome(env: &Env, match_id: &BytesN<32>) -> Result<Option<Symbol>, ContractError> {
+    if reporters(env).is_empty() {
+        return Ok(None);
+    }
+    let result = get_result(env, match_id);
+    match (result.status, result.outcome) {
+        (ResultStatus::Accepted, Some(outcome)) => Ok(Some(outcome)),
+        (ResultStatus::Frozen, _) => Err(ContractError::MatchFrozen),
+        _ => Err(ContractError::ResultNotFinal),
+    }
+}
+
+fn accept(env: &Env, match_id: &BytesN<32>, outcome: &Symbol) -> MatchResult {
+    let result = MatchResult {
+        match_id: match_id.clone(),
+        status: ResultStatus::Accepted,
+        outcome: Some(outcome.clone()),
+        decided_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::MatchResult(match_id.clone()), &result);
+
+    for (reporter, reported) in get_reports(env, match_id).iter() {
+        if reported != *outcome {
+            record_dissent(env, &reporter, match_id, &reported, outcome);
+        }
+    }
+
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events().publish(
+        (Symbol::new(env, "result_accepted"), match_id.clone()),
+        outcome.clone(),
+    );
+    result
+}
+
+fn freeze(env: &Env, match_id: &BytesN<32>) -> MatchResult {
+    let result = MatchResult {
+        match_id: match_id.clone(),
+        status: ResultStatus::Frozen,
+        outcome: None,
+        decided_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::MatchResult(match_id.clone()), &result);
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events().publish(
+        (Symbol::new(env, "match_frozen"), match_id.clone()),
+        get_reports(env, match_id),
+    );
+    result
+}
+
+fn record_dissent(
+    env: &Env,
+    reporter: &Address,
+    match_id: &BytesN<32>,
+    reported: &Symbol,
+    accepted: &Symbol,
+) {
+    let count = dissent_count(env, reporter) + 1;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Dissents(reporter.clone()), &count);
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
+    env.events().publish(
+        (Symbol::new(env, "reporter_dissented"), reporter.clone()),
+        (match_id.clone(), reported.clone(), accepted.clone()),
+    );
+}
@@ -1,514 +1,961 @@
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
 
-use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address, BytesN, Env, U256};
+use balance_ledger::{BalanceLedgerContract, BalanceLedgerContractClient};
+use betting::{
+    BettingContract, BettingContractClient, CashOutQuote, FreeBetVoucher, MatchStatus,
+    OddsPublication,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env,
+};
+
+struct Setup<'a> {
+    admin: Address,
+    backend: Address,
+    bettor: Address,
+    ledger: BalanceLedgerContractClient<'a>,
+    betting: BettingContractClient<'a>,
+    settlement: SettlementContractClient<'a>,
+}
 
-#[test]
-fn rejects_duplicate_operation_ids() {
-    let env = Env::default();
+fn setup(env: &Env) -> Setup<'_> {
     env.mock_all_auths();
 
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
-    let operation_hash = BytesN::from_array(&env, &[11u8; 32]);
+    let admin = Address::generate(env);
+    let backend = Address::generate(env);
+    let bettor = Address::generate(env);
 
-    client.initialize(&backend);
+    let ledger_id = env.register(BalanceLedgerContract, ());
+    let ledger = BalanceLedgerContractClient::new(env, &ledger_id);
+    ledger.initialize(&backend);
 
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 7),
-        &winner,
-        &1_250,
-        &None,
-    );
+    let betting_id = env.register(BettingContract, (backend.clone(), backend.clone()));
+    let betting = BettingContractClient::new(env, &betting_id);
+    let public_key = BytesN::from_array(env, &backend_key().verifying_key().to_bytes());
+    betting.rotate_backend_signer(&backend, &public_key);
 
-    assert_eq!(
-        client.try_settle_bet(
-            &operation_hash,
-            &U256::from_u32(&env, 8),
-            &winner,
-            &1_900,
-            &None,
-        ),
-        Err(Ok(ContractError::DuplicateOperation))
+    let settlement_id = env.register(
+        SettlementContract,
+        (admin.clone(), backend.clone(), ledger_id, betting_id),
     );
+    let settlement = SettlementContractClient::new(env, &settlement_id);
+    betting.set_settlement_contract(&backend, &settlement_id);
+    betting.set_treasury(&backend, &Address::generate(env));
+
+    Setup {
+        admin,
+        backend,
+        bettor,
+        ledger,
+        betting,
+        settlement,
+    }
 }
 
-#[test]
-fn supports_ttl_cleanup_for_operations() {
-    let env = Env::default();
-    env.mock_all_auths();
+fn backend_key() -> SigningKey {
+    SigningKey::from_bytes(&[42u8; 32])
+}
+
+/// Publish `odds` as the house's current price of a selection.
+fn offer_odds(s: &Setup<'_>, match_id: &BytesN<32>, bet_type: &Symbol, odds: u32) {
+    let version = s
+        .betting
+        .get_live_odds(match_id, bet_type)
+        .map_or(1, |current| current.version + 1);
+    let publication = OddsPublication {
+        match_id: match_id.clone(),
+        bet_type: bet_type.clone(),
+        odds,
+        version,
+        published_at: s.betting.env.ledger().timestamp(),
+    };
+    let message: std::vec::Vec<u8> = s
+        .betting
+        .odds_signing_payload(&publication)
+        .iter()
+        .collect();
+    let signature = BytesN::from_array(&s.betting.env, &backend_key().sign(&message).to_bytes());
+    s.betting.publish_odds(&publication, &signature);
+}
 
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
-    let operation_hash = BytesN::from_array(&env, &[22u8; 32]);
+/// Place a bet on a fresh open match and lock the stake in the ledger.
+fn place_bet(env: &Env, s: &Setup<'_>, seed: u8, amount: i128, odds: u32) -> U256 {
+    let match_id = BytesN::from_array(env, &[seed; 32]);
+    let now = env.ledger().timestamp();
+    s.betting
+        .create_match(&s.backend, &match_id, &now, &(now + 3_600));
+    s.betting
+        .update_match_status(&s.backend, &match_id, &MatchStatus::Open);
+
+    let token_admin = Address::generate(env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    s.betting
+        .set_token_config(&s.backend, &token_id, &1, &1_000_000, &7);
+    token::StellarAssetClient::new(env, &token_id).mint(&s.bettor, &amount);
+
+    let bet_type = Symbol::new(env, "home");
+    offer_odds(s, &match_id, &bet_type, odds);
+    let bet_id = s
+        .betting
+        .place_bet(&s.bettor, &token_id, &amount, &match_id, &bet_type, &odds);
+
+    let balance = s.ledger.get_balance(&s.bettor);
+    s.ledger
+        .set_balance(&s.bettor, &(balance.withdrawable + amount), &balance.locked);
+    s.ledger.lock_funds(&s.bettor, &amount);
+    bet_id
+}
 
-    client.initialize(&backend);
+#[test]
+fn computes_payouts_from_stored_odds() {
+    let env = Env::default();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 250);
 
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 5),
-        &winner,
-        &300,
-        &Some(5),
+    assert_eq!(s.settlement.compute_payout(&bet_id, &BetOutcome::Win), 250);
+    assert_eq!(s.settlement.compute_payout(&bet_id, &BetOutcome::Lose), 0);
+    assert_eq!(s.settlement.compute_payout(&bet_id, &BetOutcome::Push), 100);
+    // Half of the stake at the odds plus the other half back
+    assert_eq!(
+        s.settlement.compute_payout(&bet_id, &BetOutcome::HalfWin),
+        175
+    );
+    assert_eq!(
+        s.settlement.compute_payout(&bet_id, &BetOutcome::HalfLose),
+        50
     );
-    assert!(client.is_operation_executed(&operation_hash));
+}
 
-    env.ledger().with_mut(|li| {
-        li.timestamp += 6;
-    });
+#[test]
+fn settles_asian_handicap_half_outcomes() {
+    let env = Env::default();
+    let s = setup(&env);
 
-    assert!(client.cleanup_operation(&operation_hash));
-    assert!(!client.is_operation_executed(&operation_hash));
+    // Odd stakes leave the extra unit on the pushed half
+    let half_win = place_bet(&env, &s, 1, 101, 200);
+    let paid = s
+        .settlement
+        .settle_bet(&half_win, &151, &BetOutcome::HalfWin);
+    assert_eq!(paid, 151);
+    assert!(s.settlement.is_settled(&half_win));
 
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 6),
-        &winner,
-        &450,
-        &Some(5),
+    let half_lose = place_bet(&env, &s, 2, 101, 200);
+    assert_eq!(
+        s.settlement
+            .try_settle_bet(&half_lose, &50, &BetOutcome::HalfLose),
+        Err(Ok(ContractError::PayoutMismatch))
     );
-}
+    s.settlement
+        .settle_bet(&half_lose, &51, &BetOutcome::HalfLose);
 
-// ============================================
-// Authorization Tests - Unauthorized Calls
-// ============================================
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.locked, 0);
+    assert_eq!(balance.withdrawable, 202);
+}
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
-fn settle_bet_without_backend_auth_fails() {
+fn test_settle_win_loss_draw() {
     let env = Env::default();
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend);
+    let s = setup(&env);
+
+    // Win: locked stake released, stake * odds credited.
+    let win_id = place_bet(&env, &s, 1, 100, 200);
+    let paid = s.settlement.settle_bet(&win_id, &200, &BetOutcome::Win);
+    assert_eq!(paid, 200);
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.locked, 0);
+    assert_eq!(balance.withdrawable, 200);
+
+    assert!(s.settlement.is_settled(&win_id));
+
+    // Lose: locked stake is removed.
+    let loss_id = place_bet(&env, &s, 2, 50, 300);
+    s.settlement.settle_bet(&loss_id, &0, &BetOutcome::Lose);
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.locked, 0);
+    assert_eq!(balance.withdrawable, 200);
+
+    // Push: locked stake moves back to withdrawable.
+    let draw_id = place_bet(&env, &s, 3, 40, 180);
+    s.settlement.settle_bet(&draw_id, &40, &BetOutcome::Push);
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.locked, 0);
+    assert_eq!(balance.withdrawable, 240);
+}
 
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
+#[test]
+fn rejects_payout_that_differs_from_computed() {
+    let env = Env::default();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
 
-    // Don't mock any auths - should fail with Unauthorized
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &1000,
-        &None,
+    assert_eq!(
+        s.settlement
+            .try_settle_bet(&bet_id, &1_000, &BetOutcome::Win),
+        Err(Ok(ContractError::PayoutMismatch))
+    );
+    assert_eq!(
+        s.settlement
+            .try_settle_bet(&bet_id, &-100, &BetOutcome::Lose),
+        Err(Ok(ContractError::PayoutMismatch))
     );
+    assert!(!s.settlement.is_settled(&bet_id));
+    assert_eq!(s.ledger.get_locked(&s.bettor), 100);
 }
 
 #[test]
-#[should_panic]
-fn settle_bet_before_initialization_fails() {
+fn rejects_double_settlement() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
 
-    // Don't initialize - try to settle immediately
-    // This will panic because there's no backend signer stored
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
-
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &1000,
-        &None,
+    s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win);
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &200, &BetOutcome::Win),
+        Err(Ok(ContractError::BetAlreadySettled))
     );
 }
 
-// ============================================
-// Edge Cases
-// ============================================
-
 #[test]
-fn is_operation_executed_returns_false_for_new_operation() {
+fn rejects_cancelled_bet() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
 
-    client.initialize(&backend);
+    s.betting.cancel_bet(&s.bettor, &bet_id);
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &200, &BetOutcome::Win),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
 
-    let new_operation_hash = BytesN::from_array(&env, &[1u8; 32]);
+#[test]
+fn rejects_cashed_out_bet() {
+    let env = Env::default();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
+
+    let key = backend_key();
+    let quote = CashOutQuote {
+        bet_id: bet_id.clone(),
+        amount: 80,
+        expires_at: env.ledger().timestamp() + 60,
+        nonce: BytesN::from_array(&env, &[1u8; 32]),
+    };
+    let message: std::vec::Vec<u8> = s.betting.cash_out_signing_payload(&quote).iter().collect();
+    let signature = BytesN::from_array(&env, &key.sign(&message).to_bytes());
+    s.betting.cash_out(&s.bettor, &quote, &signature);
 
-    assert!(!client.is_operation_executed(&new_operation_hash));
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &200, &BetOutcome::Win),
+        Err(Ok(ContractError::BetCashedOut))
+    );
 }
 
 #[test]
-fn cleanup_operation_returns_false_for_nonexistent_operation() {
+fn settled_bet_is_closed_in_betting() {
     let env = Env::default();
-    env.mock_all_auths();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
+    s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win);
+
+    // Paid through the ledger, so betting can no longer pay it from escrow
+    let bet = s.betting.get_bet(&bet_id);
+    assert_eq!(bet.state, BetState::Won);
+    let token_client = token::Client::new(&env, &bet.token);
+    assert_eq!(token_client.balance(&s.betting.address), 0);
+    let treasury = s.betting.get_treasury().unwrap();
+    assert_eq!(token_client.balance(&treasury), 100);
+    assert!(s.betting.get_user_bets(&s.bettor).is_empty());
+    assert_eq!(
+        s.betting.try_claim(&s.bettor, &bet_id),
+        Err(Ok(ContractError::BetAlreadySettled))
+    );
+    assert_eq!(
+        s.betting.try_cancel_bet(&s.bettor, &bet_id),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    assert!(s
+        .betting
+        .try_mark_settled(&s.backend, &vec![&env, (bet_id.clone(), BetState::Lost, 0)])
+        .is_err());
+}
 
-    let backend = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+#[test]
+fn pending_settlement_blocks_escrow_payouts() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
+    let proposed = stake_on(&env, &s, &match_id, &token_id, &s.bettor, "home", 100);
+    let other = stake_on(&env, &s, &match_id, &token_id, &s.bettor, "away", 100);
+    s.settlement.set_dispute_window(&s.admin, &600);
 
-    client.initialize(&backend);
+    s.settlement.settle_bet(&proposed, &200, &BetOutcome::Win);
+    assert_eq!(
+        s.betting.try_cancel_bet(&s.bettor, &proposed),
+        Err(Ok(ContractError::ProposalPending))
+    );
+    let quote = CashOutQuote {
+        bet_id: proposed.clone(),
+        amount: 80,
+        expires_at: env.ledger().timestamp() + 60,
+        nonce: BytesN::from_array(&env, &[1u8; 32]),
+    };
+    assert_eq!(
+        s.betting
+            .try_cash_out(&s.bettor, &quote, &BytesN::from_array(&env, &[0u8; 64])),
+        Err(Ok(ContractError::ProposalPending))
+    );
 
-    let nonexistent_hash = BytesN::from_array(&env, &[99u8; 32]);
+    // A proposed match outcome covers every bet on the match
+    lock_match(&s, &match_id);
+    s.settlement.settle_match(&match_id, &symbol_short!("home"));
+    let quote = CashOutQuote {
+        bet_id: other.clone(),
+        nonce: BytesN::from_array(&env, &[2u8; 32]),
+        ..quote
+    };
+    assert_eq!(
+        s.betting
+            .try_cash_out(&s.bettor, &quote, &BytesN::from_array(&env, &[0u8; 64])),
+        Err(Ok(ContractError::ProposalPending))
+    );
 
-    assert!(!client.cleanup_operation(&nonexistent_hash));
+    // Voiding the match refunds nothing the settlement is handling
+    s.betting.void_match(&s.backend, &match_id, &10);
+    assert_eq!(s.betting.get_bet(&other).state, BetState::Active);
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&s.betting.address),
+        200
+    );
 }
 
 #[test]
-fn cleanup_operation_returns_false_before_ttl_expires() {
+fn guardian_can_pause_settlement() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
+    let guardian = Address::generate(&env);
 
-    client.initialize(&backend);
-
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
+    // The guardian is owned by the admin, not the backend signer
+    assert_eq!(
+        s.settlement.try_set_guardian(&s.backend, &guardian),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    for role in [&s.admin, &s.backend] {
+        assert_eq!(
+            s.settlement.try_set_guardian(&s.admin, role),
+            Err(Ok(ContractError::InvalidGuardian))
+        );
+    }
+    s.settlement.set_guardian(&s.admin, &guardian);
+    assert_eq!(s.settlement.get_guardian(), Some(guardian.clone()));
+
+    s.settlement.pause(&guardian, &PauseScope::Settlement);
+    assert!(s.settlement.is_paused(&PauseScope::Settlement));
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &200, &BetOutcome::Win),
+        Err(Ok(ContractError::Paused))
+    );
 
-    // Settle with 100 second TTL
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &1000,
-        &Some(100),
+    s.settlement.unpause(&guardian, &PauseScope::Settlement);
+    assert_eq!(
+        s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win),
+        200
     );
+}
 
-    // Advance time but not past TTL
-    env.ledger().with_mut(|li| {
-        li.timestamp += 50;
-    });
+#[test]
+fn unknown_bet_cannot_be_settled() {
+    let env = Env::default();
+    let s = setup(&env);
 
-    // Cleanup should fail since TTL hasn't expired
-    assert!(!client.cleanup_operation(&operation_hash));
-    assert!(client.is_operation_executed(&operation_hash));
+    assert!(s
+        .settlement
+        .try_settle_bet(&U256::from_u32(&env, 99), &0, &BetOutcome::Lose)
+        .is_err());
 }
 
 #[test]
-fn settle_bet_with_zero_payout_succeeds() {
+#[should_panic]
+fn settle_requires_backend_auth() {
     let env = Env::default();
-    env.mock_all_auths();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
 
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    env.set_auths(&[]);
+    s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win);
+}
 
-    client.initialize(&backend);
+/// Open a match with its own allowlisted token for several bettors.
+fn lock_match(s: &Setup<'_>, match_id: &BytesN<32>) {
+    s.betting
+        .update_match_status(&s.backend, match_id, &MatchStatus::Locked);
+}
 
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
+fn open_match(env: &Env, s: &Setup<'_>, seed: u8) -> (BytesN<32>, Address) {
+    let match_id = BytesN::from_array(env, &[seed; 32]);
+    let now = env.ledger().timestamp();
+    s.betting
+        .create_match(&s.backend, &match_id, &now, &(now + 3_600));
+    s.betting
+        .update_match_status(&s.backend, &match_id, &MatchStatus::Open);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    s.betting
+        .set_token_config(&s.backend, &token_id, &1, &1_000_000, &7);
+    (match_id, token_id)
+}
 
-    // Settle with zero payout (e.g., lost bet)
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &0,
-        &None,
-    );
+/// Place a bet at odds 2.00 on an open match and lock the stake in the ledger.
+fn stake_on(
+    env: &Env,
+    s: &Setup<'_>,
+    match_id: &BytesN<32>,
+    token_id: &Address,
+    bettor: &Address,
+    bet_type: &str,
+    amount: i128,
+) -> U256 {
+    token::StellarAssetClient::new(env, token_id).mint(bettor, &amount);
+    let bet_type = Symbol::new(env, bet_type);
+    if s.betting.get_live_odds(match_id, &bet_type).is_none() {
+        offer_odds(s, match_id, &bet_type, 200);
+    }
+    let bet_id = s
+        .betting
+        .place_bet(bettor, token_id, &amount, match_id, &bet_type, &200);
+
+    let balance = s.ledger.get_balance(bettor);
+    s.ledger
+        .set_balance(bettor, &(balance.withdrawable + amount), &balance.locked);
+    s.ledger.lock_funds(bettor, &amount);
+    bet_id
+}
 
-    assert!(client.is_operation_executed(&operation_hash));
+/// Place a 100 free bet at odds 2.00 on an open match. Free bets lock nothing
+/// in the ledger.
+fn free_bet_on(
+    env: &Env,
+    s: &Setup<'_>,
+    match_id: &BytesN<32>,
+    token_id: &Address,
+    bet_type: &str,
+    seed: u8,
+) -> U256 {
+    let voucher = FreeBetVoucher {
+        voucher_id: BytesN::from_array(env, &[seed; 32]),
+        user: s.bettor.clone(),
+        token: token_id.clone(),
+        amount: 100,
+        expires_at: env.ledger().timestamp() + 60,
+        markets: Vec::new(env),
+    };
+    let message: std::vec::Vec<u8> = s
+        .betting
+        .free_bet_signing_payload(&voucher)
+        .iter()
+        .collect();
+    let signature = BytesN::from_array(env, &backend_key().sign(&message).to_bytes());
+
+    let bet_type = Symbol::new(env, bet_type);
+    offer_odds(s, match_id, &bet_type, 200);
+    s.betting
+        .place_free_bet(&s.bettor, &voucher, &signature, match_id, &bet_type, &200)
 }
 
 #[test]
-fn settle_bet_with_large_payout_succeeds() {
+fn settles_free_bets_without_returning_the_stake() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
 
-    client.initialize(&backend);
-
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
-    let large_payout: i128 = 1_000_000_000_000;
+    let win = free_bet_on(&env, &s, &match_id, &token_id, "home", 1);
+    assert!(s.betting.get_bet(&win).free_bet);
+    assert_eq!(
+        s.settlement.try_settle_bet(&win, &200, &BetOutcome::Win),
+        Err(Ok(ContractError::PayoutMismatch))
+    );
+    assert_eq!(s.settlement.settle_bet(&win, &100, &BetOutcome::Win), 100);
 
-    // Settle with large payout
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &large_payout,
-        &None,
+    let half_win = free_bet_on(&env, &s, &match_id, &token_id, "away", 2);
+    assert_eq!(
+        s.settlement
+            .settle_bet(&half_win, &50, &BetOutcome::HalfWin),
+        50
+    );
+    let half_lose = free_bet_on(&env, &s, &match_id, &token_id, "draw", 3);
+    assert_eq!(
+        s.settlement
+            .settle_bet(&half_lose, &0, &BetOutcome::HalfLose),
+        0
     );
 
-    assert!(client.is_operation_executed(&operation_hash));
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.withdrawable, 150);
+    assert_eq!(balance.locked, 0);
+
+    // A pushed free bet in a match page pays nothing and unlocks nothing
+    let (voided, token_id) = open_match(&env, &s, 2);
+    let free = free_bet_on(&env, &s, &voided, &token_id, "home", 4);
+    stake_on(&env, &s, &voided, &token_id, &s.bettor, "away", 60);
+    lock_match(&s, &voided);
+    let progress = s.settlement.settle_match(&voided, &symbol_short!("VOID"));
+    assert!(progress.completed);
+    assert_eq!(s.settlement.get_settlement(&free).unwrap().payout, 0);
+
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.withdrawable, 210);
+    assert_eq!(balance.locked, 0);
 }
 
 #[test]
-fn settle_bet_with_negative_payout_succeeds() {
+fn settles_match_in_pages_and_resumes_from_cursor() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend);
-
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
-    let negative_payout: i128 = -500;
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
+    let other = Address::generate(&env);
+
+    let count = MATCH_SETTLEMENT_PAGE + 5;
+    for index in 0..count {
+        let (bettor, bet_type) = if index % 2 == 0 {
+            (&s.bettor, "home")
+        } else {
+            (&other, "away")
+        };
+        stake_on(&env, &s, &match_id, &token_id, bettor, bet_type, 10);
+    }
+    let home = Symbol::new(&env, "home");
+
+    // Bets are only settled once betting on the match has closed
+    assert_eq!(
+        s.settlement.try_settle_match(&match_id, &home),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    assert_eq!(s.settlement.get_match_settlement(&match_id), None);
+    lock_match(&s, &match_id);
 
-    // Settle with negative payout (e.g., penalty)
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &negative_payout,
-        &None,
+    let first = s.settlement.settle_match(&match_id, &home);
+    assert_eq!(first.next_index, MATCH_SETTLEMENT_PAGE);
+    assert!(!first.completed);
+    assert_eq!(
+        s.settlement
+            .try_settle_match(&match_id, &Symbol::new(&env, "away")),
+        Err(Ok(ContractError::InvalidStatus))
     );
 
-    assert!(client.is_operation_executed(&operation_hash));
+    let second = s.settlement.settle_match(&match_id, &home);
+    assert_eq!(second.next_index, count);
+    assert_eq!(second.settled_bets, count);
+    assert!(second.completed);
+    assert_eq!(s.settlement.get_match_settlement(&match_id), Some(second));
+
+    // 15 winning home bets pay 20 each; the 15 away stakes are lost
+    let winner = s.ledger.get_balance(&s.bettor);
+    assert_eq!(winner.withdrawable, 300);
+    assert_eq!(winner.locked, 0);
+    let loser = s.ledger.get_balance(&other);
+    assert_eq!(loser.withdrawable, 0);
+    assert_eq!(loser.locked, 0);
 }
 
 #[test]
-fn settle_bet_with_zero_ttl_immediate_cleanup() {
+fn settle_match_voids_and_skips_settled_bets() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend);
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
+
+    let settled = stake_on(&env, &s, &match_id, &token_id, &s.bettor, "home", 40);
+    let open = stake_on(&env, &s, &match_id, &token_id, &s.bettor, "away", 60);
+    s.settlement.settle_bet(&settled, &0, &BetOutcome::Lose);
+
+    lock_match(&s, &match_id);
+    let progress = s.settlement.settle_match(&match_id, &symbol_short!("VOID"));
+    assert!(progress.completed);
+    assert_eq!(progress.settled_bets, 1);
+    assert!(s.settlement.is_settled(&open));
+
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.withdrawable, 60);
+    assert_eq!(balance.locked, 0);
+}
 
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
+#[test]
+fn proposed_settlement_moves_funds_only_after_dispute_window() {
+    let env = Env::default();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
+    s.settlement.set_dispute_window(&s.admin, &600);
 
-    // Settle with 0 TTL - operation is immediately expired per is_expired logic
-    // (timestamp - executed_at >= 0 is always true when timestamp >= executed_at)
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &1000,
-        &Some(0),
+    assert_eq!(
+        s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win),
+        200
+    );
+    let proposal = s.settlement.get_proposal(&bet_id).unwrap();
+    assert_eq!(proposal.finalizes_at, env.ledger().timestamp() + 600);
+    assert!(!s.settlement.is_settled(&bet_id));
+    assert_eq!(s.ledger.get_balance(&s.bettor).locked, 100);
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &0, &BetOutcome::Lose),
+        Err(Ok(ContractError::ProposalPending))
+    );
+    assert_eq!(
+        s.settlement.try_finalize_settlement(&bet_id),
+        Err(Ok(ContractError::ResultNotFinal))
     );
 
-    // With TTL of 0, the operation is considered expired immediately
-    // so it won't be stored (it gets cleaned up during ensure_not_replayed)
-    // This is existing contract behavior, not a bug
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    assert_eq!(s.settlement.finalize_settlement(&bet_id), 200);
+    assert!(s.settlement.is_settled(&bet_id));
+    assert_eq!(s.settlement.get_proposal(&bet_id), None);
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.withdrawable, 200);
+    assert_eq!(balance.locked, 0);
 }
 
 #[test]
-fn multiple_bets_same_winner_different_operation_hashes() {
+fn disputer_rejects_settlement_within_window() {
     let env = Env::default();
-    env.mock_all_auths();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
+    let disputer = Address::generate(&env);
+    s.settlement.set_dispute_window(&s.admin, &600);
+    s.settlement.set_disputer(&s.admin, &disputer);
 
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win);
+    assert_eq!(
+        s.settlement.try_reject_settlement(&s.bettor, &bet_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    s.settlement.reject_settlement(&disputer, &bet_id);
+    assert_eq!(
+        s.settlement.try_finalize_settlement(&bet_id),
+        Err(Ok(ContractError::ProposalNotFound))
+    );
 
-    client.initialize(&backend);
+    // The corrected result can be proposed again
+    s.settlement.settle_bet(&bet_id, &0, &BetOutcome::Lose);
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    assert_eq!(
+        s.settlement.try_reject_settlement(&s.backend, &bet_id),
+        Err(Ok(ContractError::DisputeWindowClosed))
+    );
+    s.settlement.finalize_settlement(&bet_id);
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.withdrawable, 0);
+    assert_eq!(balance.locked, 0);
+}
 
-    // Settle multiple bets for the same winner
-    let operation_hash1 = BytesN::from_array(&env, &[1u8; 32]);
-    let operation_hash2 = BytesN::from_array(&env, &[2u8; 32]);
-    let operation_hash3 = BytesN::from_array(&env, &[3u8; 32]);
+#[test]
+fn admin_manages_dispute_settings_and_can_reject() {
+    let env = Env::default();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
 
-    client.settle_bet(
-        &operation_hash1,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &100,
-        &None,
+    assert_eq!(
+        s.settlement.try_set_dispute_window(&s.backend, &600),
+        Err(Ok(ContractError::Unauthorized))
     );
-    client.settle_bet(
-        &operation_hash2,
-        &U256::from_u32(&env, 2),
-        &winner,
-        &200,
-        &None,
+    assert_eq!(
+        s.settlement.try_set_disputer(&s.backend, &s.bettor),
+        Err(Ok(ContractError::Unauthorized))
     );
-    client.settle_bet(
-        &operation_hash3,
-        &U256::from_u32(&env, 3),
-        &winner,
-        &300,
-        &None,
+
+    // A window running past the end of time is rejected rather than wrapping
+    s.settlement.set_dispute_window(&s.admin, &u64::MAX);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &200, &BetOutcome::Win),
+        Err(Ok(ContractError::InvalidTimeWindow))
     );
 
-    assert!(client.is_operation_executed(&operation_hash1));
-    assert!(client.is_operation_executed(&operation_hash2));
-    assert!(client.is_operation_executed(&operation_hash3));
+    s.settlement.set_dispute_window(&s.admin, &600);
+    s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win);
+    s.settlement.reject_settlement(&s.admin, &bet_id);
+    assert_eq!(s.settlement.get_proposal(&bet_id), None);
 }
 
 #[test]
-fn same_operation_hash_different_bet_ids_fails() {
+fn match_outcome_waits_for_dispute_window() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
+    stake_on(&env, &s, &match_id, &token_id, &s.bettor, "home", 50);
+    s.settlement.set_dispute_window(&s.admin, &600);
+    lock_match(&s, &match_id);
+
+    let away = Symbol::new(&env, "away");
+    let proposed = s.settlement.settle_match(&match_id, &away);
+    assert_eq!(proposed.next_index, 0);
+    assert!(!proposed.completed);
+    s.settlement.reject_match_settlement(&s.backend, &match_id);
+    assert_eq!(s.settlement.get_match_settlement(&match_id), None);
+
+    let home = Symbol::new(&env, "home");
+    s.settlement.settle_match(&match_id, &home);
+    assert_eq!(s.ledger.get_balance(&s.bettor).locked, 50);
+
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    let settled = s.settlement.settle_match(&match_id, &home);
+    assert!(settled.completed);
+    let balance = s.ledger.get_balance(&s.bettor);
+    assert_eq!(balance.withdrawable, 100);
+    assert_eq!(balance.locked, 0);
+}
 
-    client.initialize(&backend);
+fn register_reporters(env: &Env, s: &Setup<'_>, count: u32, quorum: u32) -> std::vec::Vec<Address> {
+    let reporters: std::vec::Vec<Address> = (0..count).map(|_| Address::generate(env)).collect();
+    for reporter in &reporters {
+        s.settlement.add_reporter(&s.admin, reporter);
+    }
+    s.settlement.set_quorum(&s.admin, &quorum);
+    reporters
+}
 
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
+#[test]
+fn accepts_result_at_quorum_and_records_dissenters() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
+    stake_on(&env, &s, &match_id, &token_id, &s.bettor, "home", 50);
+    let reporters = register_reporters(&env, &s, 3, 2);
+    let home = Symbol::new(&env, "home");
+    let away = Symbol::new(&env, "away");
+    lock_match(&s, &match_id);
 
-    // First settlement succeeds
-    client.settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 1),
-        &winner,
-        &100,
-        &None,
+    assert_eq!(
+        s.settlement.try_settle_match(&match_id, &home),
+        Err(Ok(ContractError::ResultNotFinal))
     );
 
-    // Second settlement with same operation hash but different bet_id should fail
-    let result = client.try_settle_bet(
-        &operation_hash,
-        &U256::from_u32(&env, 2),
-        &winner,
-        &200,
-        &None,
+    let first = s.settlement.submit_result(&reporters[0], &match_id, &home);
+    assert_eq!(first.status, ResultStatus::Pending);
+    s.settlement.submit_result(&reporters[1], &match_id, &away);
+    assert_eq!(
+        s.settlement
+            .try_submit_result(&reporters[1], &match_id, &home),
+        Err(Ok(ContractError::DuplicateOperation))
     );
+    let accepted = s.settlement.submit_result(&reporters[2], &match_id, &home);
+    assert_eq!(accepted.status, ResultStatus::Accepted);
+    assert_eq!(accepted.outcome, Some(home.clone()));
+
+    assert_eq!(s.settlement.get_dissent_count(&reporters[0]), 0);
+    assert_eq!(s.settlement.get_dissent_count(&reporters[1]), 1);
 
-    assert_eq!(result, Err(Ok(ContractError::DuplicateOperation)));
+    assert_eq!(
+        s.settlement.try_settle_match(&match_id, &away),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    assert!(s.settlement.settle_match(&match_id, &home).completed);
+    assert_eq!(s.ledger.get_balance(&s.bettor).withdrawable, 100);
 }
 
 #[test]
-fn different_winners_same_operation_hash_isolation() {
+fn admin_corrects_away_from_wrongly_accepted_result() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let winner1 = Address::generate(&env);
-    let winner2 = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let reporters = register_reporters(&env, &s, 1, 1);
+    s.settlement
+        .submit_result(&reporters[0], &match_id, &Symbol::new(&env, "away"));
+    s.settlement.settle_bet(&bet_id, &0, &BetOutcome::Lose);
+
+    // The match actually went home
+    s.settlement
+        .resettle_bet(&s.admin, &bet_id, &200, &BetOutcome::Win);
+    assert_eq!(
+        s.settlement.get_settlement(&bet_id).unwrap().outcome,
+        BetOutcome::Win
+    );
+    assert_eq!(s.ledger.get_balance(&s.bettor).withdrawable, 200);
+}
 
-    client.initialize(&backend);
+#[test]
+fn half_outcomes_need_admin_approval_once_reporters_decide() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
+    let bet_id = stake_on(&env, &s, &match_id, &token_id, &s.bettor, "home", 100);
+    let reporters = register_reporters(&env, &s, 1, 1);
+    s.settlement
+        .submit_result(&reporters[0], &match_id, &symbol_short!("home"));
 
-    let operation_hash1 = BytesN::from_array(&env, &[1u8; 32]);
-    let operation_hash2 = BytesN::from_array(&env, &[2u8; 32]);
+    assert_eq!(
+        s.settlement
+            .try_settle_bet(&bet_id, &150, &BetOutcome::HalfWin),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    assert_eq!(
+        s.settlement
+            .try_approve_half_outcome(&s.backend, &bet_id, &BetOutcome::HalfWin),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        s.settlement
+            .try_approve_half_outcome(&s.admin, &bet_id, &BetOutcome::Win),
+        Err(Ok(ContractError::InvalidStatus))
+    );
 
-    // Settle bets for different winners
-    client.settle_bet(
-        &operation_hash1,
-        &U256::from_u32(&env, 1),
-        &winner1,
-        &100,
-        &None,
+    s.settlement
+        .approve_half_outcome(&s.admin, &bet_id, &BetOutcome::HalfWin);
+    assert_eq!(
+        s.settlement.get_half_outcome(&bet_id),
+        Some(BetOutcome::HalfWin)
+    );
+    assert_eq!(
+        s.settlement
+            .try_settle_bet(&bet_id, &50, &BetOutcome::HalfLose),
+        Err(Ok(ContractError::InvalidStatus))
     );
-    client.settle_bet(
-        &operation_hash2,
-        &U256::from_u32(&env, 2),
-        &winner2,
-        &200,
-        &None,
+    assert_eq!(
+        s.settlement.settle_bet(&bet_id, &150, &BetOutcome::HalfWin),
+        150
     );
 
-    assert!(client.is_operation_executed(&operation_hash1));
-    assert!(client.is_operation_executed(&operation_hash2));
+    // Corrections to a half outcome need the same sign-off
+    assert_eq!(
+        s.settlement
+            .try_resettle_bet(&s.admin, &bet_id, &50, &BetOutcome::HalfLose),
+        Err(Ok(ContractError::InvalidStatus))
+    );
 }
 
 #[test]
-fn large_bet_id_handling() {
+fn conflicting_reports_freeze_match_until_reviewed() {
     let env = Env::default();
-    env.mock_all_auths();
-
-    let backend = Address::generate(&env);
-    let winner = Address::generate(&env);
-    let contract_id = env.register(SettlementContract, ());
-    let client = SettlementContractClient::new(&env, &contract_id);
-
-    client.initialize(&backend);
+    let s = setup(&env);
+    let (match_id, token_id) = open_match(&env, &s, 1);
+    let bet_id = stake_on(&env, &s, &match_id, &token_id, &s.bettor, "home", 50);
+    let reporters = register_reporters(&env, &s, 3, 3);
+    let home = Symbol::new(&env, "home");
+    let draw = Symbol::new(&env, "draw");
+    let stranger = Address::generate(&env);
 
-    let operation_hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        s.settlement.try_submit_result(&stranger, &match_id, &home),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    s.settlement.submit_result(&reporters[0], &match_id, &home);
+    let frozen = s.settlement.submit_result(&reporters[1], &match_id, &draw);
+    assert_eq!(frozen.status, ResultStatus::Frozen);
+    assert_eq!(
+        s.settlement
+            .try_submit_result(&reporters[2], &match_id, &home),
+        Err(Ok(ContractError::MatchFrozen))
+    );
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &100, &BetOutcome::Win),
+        Err(Ok(ContractError::MatchFrozen))
+    );
 
-    // Use a large U256 value for bet_id
-    let large_bet_id = U256::from_u128(&env, u128::MAX);
+    let resolved = s
+        .settlement
+        .resolve_frozen_match(&s.admin, &match_id, &draw);
+    assert_eq!(resolved.status, ResultStatus::Accepted);
+    assert_eq!(s.settlement.get_dissent_count(&reporters[0]), 1);
+    assert_eq!(
+        s.settlement.try_settle_bet(&bet_id, &100, &BetOutcome::Win),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    s.settlement.settle_bet(&bet_id, &0, &BetOutcome::Lose);
+}
 
-    client.settle_bet(&operation_hash, &large_bet_id, &winner, &1000, &None);
+#[test]
+fn quorum_must_fit_registered_reporters() {
+    let env = Env::default();
+    let s = setup(&env);
+    let reporters = register_reporters(&env, &s, 2, 2);
 
-    assert!(client.is_operation_executed(&operation_hash));
+    assert_eq!(
+        s.settlement.try_set_quorum(&s.backend, &1),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        s.settlement.try_set_quorum(&s.admin, &3),
+        Err(Ok(ContractError::InvalidQuorum))
+    );
+    assert_eq!(
+        s.settlement.try_remove_reporter(&s.admin, &reporters[0]),
+        Err(Ok(ContractError::InvalidQuorum))
+    );
+    s.settlement.set_quorum(&s.admin, &1);
+    s.settlement.remove_reporter(&s.admin, &reporters[0]);
+    assert_eq!(s.settlement.get_reporters().len(), 1);
 }
 
 #[test]
-fn test_settle_win_loss_draw() {
-    use soroban_sdk::{testutils::Address as _, Address};
-
+fn resettles_bet_with_compensating_deltas_and_revision_history() {
     let env = Env::default();
-    env.mock_all_auths();
+    let s = setup(&env);
+    let bet_id = place_bet(&env, &s, 1, 100, 200);
+
+    assert_eq!(
+        s.settlement
+            .try_resettle_bet(&s.admin, &bet_id, &0, &BetOutcome::Lose),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    s.settlement.settle_bet(&bet_id, &200, &BetOutcome::Win);
+    assert_eq!(
+        s.settlement
+            .try_resettle_bet(&s.admin, &bet_id, &200, &BetOutcome::Win),
+        Err(Ok(ContractError::DuplicateOperation))
+    );
+    // Corrections are the admin's call, not the backend signer's
+    assert_eq!(
+        s.settlement
+            .try_resettle_bet(&s.backend, &bet_id, &0, &BetOutcome::Lose),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        s.settlement
+            .try_resettle_bet(&s.admin, &bet_id, &100, &BetOutcome::Lose),
+        Err(Ok(ContractError::PayoutMismatch))
+    );
 
-    let backend = Address::generate(&env);
-
-    // Deploy balance ledger and initialize
-    let bl_contract_id = env.register(balance_ledger::BalanceLedgerContract, ());
-    let bl_client = balance_ledger::BalanceLedgerContractClient::new(&env, &bl_contract_id);
-    bl_client.initialize(&backend);
-
-    // Deploy settlement contract and initialize with balance ledger address
-    let st_contract_id = env.register(SettlementContract, ());
-    let st_client = SettlementContractClient::new(&env, &st_contract_id);
-    let bl_addr = Address::Contract(bl_contract_id.clone());
-    st_client.initialize(&backend, &bl_addr);
-
-    // Prepare bettor and winner
-    let bettor = Address::generate(&env);
-    let winner = Address::generate(&env);
-
-    // Fund and lock bettor funds
-    bl_client.set_balance(&bettor, &1_000, &0);
-    bl_client.lock_funds(&bettor, &100);
-
-    let bet_id = U256::from_u64(42);
-
-    // Settle WIN: bettor locked 100 -> winner gets payout 200
-    let win_sym = soroban_sdk::Symbol::short("WIN");
-    let res = st_client.settle_bet(
-        &bet_id,
-        &bettor,
-        &Some(winner.clone()),
-        &100,
-        &200,
-        &win_sym,
-    );
-    assert!(res.is_ok());
-
-    // Check balances: bettor locked decreased by 100, winner withdrawable increased by 200
-    let bettor_balance = bl_client.get_balance(&bettor);
-    assert_eq!(bettor_balance.locked, 0);
-
-    let winner_balance = bl_client.get_withdrawable(&winner);
-    assert_eq!(winner_balance, 200);
-
-    // Attempt to re-settle same bet -> should fail
-    let res2 = st_client.try_settle_bet(
-        &bet_id,
-        &bettor,
-        &Some(winner.clone()),
-        &100,
-        &200,
-        &win_sym,
-    );
-    assert!(res2.is_err());
-
-    // Test DRAW / refund for another bet
-    let bet_id2 = U256::from_u64(43);
-    bl_client.set_balance(&bettor, &500, &0);
-    bl_client.lock_funds(&bettor, &50);
-    let draw_sym = soroban_sdk::Symbol::short("DRAW");
-    let res3 = st_client.settle_bet(&bet_id2, &bettor, &None, &50, &0, &draw_sym);
-    assert!(res3.is_ok());
-
-    let after_refund = bl_client.get_balance(&bettor);
-    assert_eq!(after_refund.withdrawable, 500);
-    assert_eq!(after_refund.locked, 0);
+    // The bettor withdrew most of the winnings before the correction
+    s.ledger.set_balance(&s.bettor, &50, &0);
+    assert_eq!(
+        s.settlement
+            .resettle_bet(&s.admin, &bet_id, &0, &BetOutcome::Lose),
+        -200
+    );
+    assert_eq!(s.ledger.get_balance(&s.bettor).withdrawable, 0);
+    assert_eq!(s.ledger.get_receivable(&s.bettor), 150);
+
+    let record = s.settlement.get_settlement(&bet_id).unwrap();
+    assert_eq!(record.outcome, BetOutcome::Lose);
+    assert_eq!(record.winner, None);
+    assert_eq!(record.revisions.len(), 1);
+    assert_eq!(record.revisions.get(0).unwrap().outcome, BetOutcome::Win);
+    assert_eq!(record.revisions.get(0).unwrap().payout, 200);
+    assert_eq!(s.betting.get_bet(&bet_id).state, BetState::Lost);
+
+    // Refunding the stake goes to the receivable first
+    assert_eq!(
+        s.settlement
+            .resettle_bet(&s.admin, &bet_id, &100, &BetOutcome::Push),
+        100
+    );
+    assert_eq!(s.ledger.get_balance(&s.bettor).withdrawable, 0);
+    assert_eq!(s.ledger.get_receivable(&s.bettor), 50);
+    assert_eq!(
+        s.settlement
+            .get_settlement(&bet_id)
+            .unwrap()
+            .revisions
+            .len(),
+        2
+    );
 }
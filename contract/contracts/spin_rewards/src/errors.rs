@@ -8,4 +8,6 @@ pub enum RewardError {
     ExceedsPerSpinCap = 3,
     ExceedsUserCap = 4,
     ExceedsTotalCap = 5,
+    Paused = 6,
+    InvalidGuardian = 7,
 }
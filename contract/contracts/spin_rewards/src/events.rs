@@ -1,6 +1,7 @@
 use soroban_sdk::{symbol_short, Env, Address};
 
 pub fn reward_distributed(env: &Env, user: Address, amount: i128) {
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
     env.events().publish(
         (symbol_short!("reward"),),
         (user, amount),
@@ -8,6 +9,7 @@ pub fn reward_distributed(env: &Env, user: Address, amount: i128) {
 }
 
 pub fn pool_funded(env: &Env, amount: i128) {
+    #[allow(deprecated)] // keep (topic, payload) format for indexer compatibility
     env.events().publish(
         (symbol_short!("funded"),),
         amount,
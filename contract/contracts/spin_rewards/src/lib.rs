@@ -9,6 +9,7 @@ mod events;
 mod nft;
 
 use storage::{DataKey, RewardConfig};
+use common::PauseScope;
 use errors::RewardError;
 
 #[contract]
@@ -35,6 +36,8 @@ impl SpinRewards {
         user: Address,
         amount: i128,
     ) -> Result<(), RewardError> {
+        Self::ensure_not_paused(&env, PauseScope::Spins)?;
+        Self::ensure_not_paused(&env, PauseScope::Withdrawals)?;
         reward::distribute_xlm(&env, user, amount)
     }
 
@@ -42,7 +45,60 @@ impl SpinRewards {
         env: Env,
         nft_contract: Address,
         user: Address,
-    ) {
+    ) -> Result<(), RewardError> {
+        Self::ensure_not_paused(&env, PauseScope::Spins)?;
         nft::mint_nft(&env, nft_contract, user);
+        Ok(())
+    }
+
+    /// Set the guardian allowed to pause rewards. Only the admin can set it,
+    /// and the guardian must be a different address.
+    pub fn set_guardian(env: Env, guardian: Address) -> Result<(), RewardError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(RewardError::NotAuthorized)?;
+        admin.require_auth();
+        if guardian == admin {
+            return Err(RewardError::InvalidGuardian);
+        }
+        common::set_guardian(&env, &guardian);
+        Ok(())
+    }
+
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        common::get_guardian(&env)
+    }
+
+    pub fn pause(env: Env, guardian: Address, scope: PauseScope) -> Result<(), RewardError> {
+        Self::set_paused(&env, guardian, scope, true)
+    }
+
+    pub fn unpause(env: Env, guardian: Address, scope: PauseScope) -> Result<(), RewardError> {
+        Self::set_paused(&env, guardian, scope, false)
+    }
+
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        common::is_paused(&env, scope)
+    }
+
+    fn set_paused(
+        env: &Env,
+        guardian: Address,
+        scope: PauseScope,
+        paused: bool,
+    ) -> Result<(), RewardError> {
+        if common::get_guardian(env) != Some(guardian.clone()) {
+            return Err(RewardError::NotAuthorized);
+        }
+        guardian.require_auth();
+        common::set_paused(env, scope, paused);
+        Ok(())
+    }
+
+    fn ensure_not_paused(env: &Env, scope: PauseScope) -> Result<(), RewardError> {
+        if common::is_paused(env, scope) {
+            return Err(RewardError::Paused);
+        }
+        Ok(())
     }
 }
+
+mod test;
@@ -1,13 +1,13 @@
-use soroban_sdk::{Env, Address, Symbol};
+use soroban_sdk::{vec, Env, Address, IntoVal, Symbol};
 
 pub fn mint_nft(
     env: &Env,
     nft_contract: Address,
     to: Address,
 ) {
-    env.invoke_contract(
+    env.invoke_contract::<()>(
         &nft_contract,
         &Symbol::new(env, "mint"),
-        (to,)
+        vec![env, to.into_val(env)],
     );
 }
@@ -0,0 +1,106 @@
+#![cfg(test)]
+
+use super::*;
+
+use soroban_sdk::testutils::Address as _;
+
+fn setup(env: &Env) -> (SpinRewardsClient<'_>, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let contract_id = env.register(SpinRewards, ());
+    let client = SpinRewardsClient::new(env, &contract_id);
+    client.init(
+        &admin,
+        &RewardConfig {
+            max_per_spin: 100,
+            max_per_user: 1_000,
+            total_cap: 10_000,
+        },
+    );
+    (client, admin)
+}
+
+#[test]
+fn guardian_must_differ_from_admin() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let guardian = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_guardian(&admin),
+        Err(Ok(RewardError::InvalidGuardian))
+    );
+    client.set_guardian(&guardian);
+    assert_eq!(client.get_guardian(), Some(guardian));
+}
+
+#[test]
+fn guardian_needs_an_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(SpinRewards, ());
+    let client = SpinRewardsClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_set_guardian(&Address::generate(&env)),
+        Err(Ok(RewardError::NotAuthorized))
+    );
+}
+
+#[test]
+fn only_guardian_can_pause() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let guardian = Address::generate(&env);
+    client.set_guardian(&guardian);
+
+    assert_eq!(
+        client.try_pause(&admin, &PauseScope::Spins),
+        Err(Ok(RewardError::NotAuthorized))
+    );
+    assert!(!client.is_paused(&PauseScope::Spins));
+
+    client.pause(&guardian, &PauseScope::Spins);
+    assert!(client.is_paused(&PauseScope::Spins));
+    assert_eq!(
+        client.try_unpause(&admin, &PauseScope::Spins),
+        Err(Ok(RewardError::NotAuthorized))
+    );
+}
+
+#[test]
+fn paused_scopes_block_rewards() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let guardian = Address::generate(&env);
+    let user = Address::generate(&env);
+    let nft_contract = Address::generate(&env);
+    client.set_guardian(&guardian);
+
+    client.pause(&guardian, &PauseScope::Spins);
+    assert_eq!(
+        client.try_reward_xlm(&user, &50),
+        Err(Ok(RewardError::Paused))
+    );
+    assert_eq!(
+        client.try_reward_nft(&nft_contract, &user),
+        Err(Ok(RewardError::Paused))
+    );
+    client.unpause(&guardian, &PauseScope::Spins);
+
+    // Withdrawals only hold back XLM rewards
+    client.pause(&guardian, &PauseScope::Withdrawals);
+    assert_eq!(
+        client.try_reward_xlm(&user, &50),
+        Err(Ok(RewardError::Paused))
+    );
+    client.unpause(&guardian, &PauseScope::Withdrawals);
+    assert!(!client.is_paused(&PauseScope::Withdrawals));
+
+    // Once unpaused, rewards reach the usual cap checks
+    assert_eq!(
+        client.try_reward_xlm(&user, &500),
+        Err(Ok(RewardError::ExceedsPerSpinCap))
+    );
+}
@@ -13,6 +13,12 @@ BUILD_DIR="target/wasm32-unknown-unknown/release"
 LOG_FILE="deployment-$(date +%Y%m%d-%H%M%S).log"
 CONTRACTS=("settlement" "betting" "balance_ledger" "player_card" "staking")
 DEPLOYMENT_STATE_FILE="deployment-state.json"
+# Roles passed to the betting contract's constructor
+BETTING_ADMIN="${BETTING_ADMIN:-}"
+BETTING_OPERATOR="${BETTING_OPERATOR:-}"
+# Roles passed to the settlement contract's constructor
+SETTLEMENT_ADMIN="${SETTLEMENT_ADMIN:-}"
+SETTLEMENT_BACKEND="${SETTLEMENT_BACKEND:-}"
 
 # Color codes for output
 RED='\033[0;31m'
@@ -143,6 +149,31 @@ deploy_contract() {
         fi
     fi
     
+    # Contracts with a constructor take their arguments at deployment
+    local constructor_args=()
+    if [ "$contract_name" = "betting" ]; then
+        if [ -z "$BETTING_ADMIN" ] || [ -z "$BETTING_OPERATOR" ]; then
+            print_error "BETTING_ADMIN and BETTING_OPERATOR must be set to deploy betting"
+            return 1
+        fi
+        constructor_args=(-- --admin "$BETTING_ADMIN" --operator "$BETTING_OPERATOR")
+    elif [ "$contract_name" = "settlement" ]; then
+        if [ -z "$SETTLEMENT_ADMIN" ] || [ -z "$SETTLEMENT_BACKEND" ]; then
+            print_error "SETTLEMENT_ADMIN and SETTLEMENT_BACKEND must be set to deploy settlement"
+            return 1
+        fi
+        # Settlement is wired to the ledger and betting contracts deployed before it
+        local ledger_id betting_id
+        ledger_id=$(jq -r '.contracts.balance_ledger.address // empty' "$DEPLOYMENT_STATE_FILE" 2>/dev/null)
+        betting_id=$(jq -r '.contracts.betting.address // empty' "$DEPLOYMENT_STATE_FILE" 2>/dev/null)
+        if [ -z "$ledger_id" ] || [ -z "$betting_id" ]; then
+            print_error "balance_ledger and betting must be deployed before settlement"
+            return 1
+        fi
+        constructor_args=(-- --admin "$SETTLEMENT_ADMIN" --backend "$SETTLEMENT_BACKEND" \
+            --ledger "$ledger_id" --betting "$betting_id")
+    fi
+
     # Deploy the contract
     print_status "Deploying $contract_name..."
     local contract_id
@@ -151,7 +182,8 @@ deploy_contract() {
         --source "$SOURCE_ACCOUNT" \
         --network "$NETWORK" \
         --rpc-url "$RPC_URL" \
-        --network-passphrase "$PASSPHRASE")
+        --network-passphrase "$PASSPHRASE" \
+        "${constructor_args[@]}")
     
     if [ $? -eq 0 ]; then
         print_success "$contract_name deployed successfully at $contract_id"